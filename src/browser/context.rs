@@ -2,13 +2,14 @@ use std::sync::Arc;
 
 use failure::Fallible;
 
-use crate::browser::tab::Tab;
+use crate::browser::tab::{EmulationOptions, Tab};
 use crate::protocol::target::methods::CreateTarget;
 
 /// Equivalent to a new incognito window
 pub struct Context<'a> {
     id: String,
     browser: &'a super::Browser,
+    emulation_options: EmulationOptions,
 }
 
 impl<'a> Context<'a> {
@@ -16,9 +17,17 @@ impl<'a> Context<'a> {
         Self {
             id: context_id,
             browser,
+            emulation_options: EmulationOptions::default(),
         }
     }
 
+    /// Sets the timezone, locale and other `EmulationOptions` every tab opened with `new_tab`
+    /// starts out with.
+    pub fn with_emulation_options(mut self, emulation_options: EmulationOptions) -> Self {
+        self.emulation_options = emulation_options;
+        self
+    }
+
     /// Opens a new tab in this context. It will not share cookies or a cache with the default
     /// browsing context or any other contexts created
     pub fn new_tab(&self) -> Fallible<Arc<Tab>> {
@@ -29,7 +38,9 @@ impl<'a> Context<'a> {
             browser_context_id: Some(&self.id),
             enable_begin_frame_control: None,
         };
-        self.browser.new_tab_with_options(tab_in_context)
+        let tab = self.browser.new_tab_with_options(tab_in_context)?;
+        tab.set_emulation_options(&self.emulation_options)?;
+        Ok(tab)
     }
 
     /// The BrowserContextId associated with this context
@@ -0,0 +1,384 @@
+//! Resolving channels, milestones and versions to downloads, using the JSON endpoints of
+//! [Chrome for Testing](https://github.com/GoogleChromeLabs/chrome-for-testing).
+
+use std::{collections::HashMap, path::PathBuf, str::FromStr};
+
+use failure::{format_err, Error, Fallible};
+use serde::Deserialize;
+
+pub(crate) const DEFAULT_MANIFEST_BASE_URL: &str =
+    "https://googlechromelabs.github.io/chrome-for-testing";
+
+#[cfg(target_os = "linux")]
+pub(crate) const PLATFORM: &str = "linux64";
+#[cfg(all(target_os = "macos", target_arch = "aarch64"))]
+pub(crate) const PLATFORM: &str = "mac-arm64";
+#[cfg(all(target_os = "macos", not(target_arch = "aarch64")))]
+pub(crate) const PLATFORM: &str = "mac-x64";
+#[cfg(all(windows, target_pointer_width = "64"))]
+pub(crate) const PLATFORM: &str = "win64";
+#[cfg(all(windows, not(target_pointer_width = "64")))]
+pub(crate) const PLATFORM: &str = "win32";
+
+/// A Chrome release channel.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Channel {
+    Stable,
+    Beta,
+    Dev,
+    Canary,
+}
+
+impl Channel {
+    fn manifest_key(self) -> &'static str {
+        match self {
+            Self::Stable => "Stable",
+            Self::Beta => "Beta",
+            Self::Dev => "Dev",
+            Self::Canary => "Canary",
+        }
+    }
+}
+
+impl FromStr for Channel {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Fallible<Self> {
+        match s.to_lowercase().as_str() {
+            "stable" => Ok(Self::Stable),
+            "beta" => Ok(Self::Beta),
+            "dev" => Ok(Self::Dev),
+            "canary" => Ok(Self::Canary),
+            _ => Err(format_err!("Unknown channel: {}", s)),
+        }
+    }
+}
+
+/// Which of the binaries published for a Chrome for Testing version to fetch.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Artifact {
+    /// The full Chrome browser.
+    Chrome,
+    /// The standalone `chrome-headless-shell`, which only implements the old headless mode but
+    /// is a lot smaller.
+    HeadlessShell,
+}
+
+impl Default for Artifact {
+    fn default() -> Self {
+        Self::Chrome
+    }
+}
+
+impl Artifact {
+    fn manifest_key(self) -> &'static str {
+        match self {
+            Self::Chrome => "chrome",
+            Self::HeadlessShell => "chrome-headless-shell",
+        }
+    }
+
+    // the name of the archive, which is also the directory it unpacks into
+    pub(crate) fn archive_name(self) -> String {
+        format!("{}-{}", self.manifest_key(), PLATFORM)
+    }
+
+    // what follows `{PLATFORM}-` in the name of the installation directory for `version`
+    pub(crate) fn install_key(self, version: &str) -> String {
+        match self {
+            Self::Chrome => version.to_string(),
+            Self::HeadlessShell => format!("{}_headless_shell", version),
+        }
+    }
+
+    // path of the executable, relative to the installation directory
+    pub(crate) fn executable(self) -> PathBuf {
+        let mut path = PathBuf::from(self.archive_name());
+        match self {
+            Self::Chrome => {
+                #[cfg(target_os = "linux")]
+                path.push("chrome");
+                #[cfg(target_os = "macos")]
+                path.extend(&[
+                    "Google Chrome for Testing.app",
+                    "Contents",
+                    "MacOS",
+                    "Google Chrome for Testing",
+                ]);
+                #[cfg(windows)]
+                path.push("chrome.exe");
+            }
+            Self::HeadlessShell => {
+                #[cfg(not(windows))]
+                path.push("chrome-headless-shell");
+                #[cfg(windows)]
+                path.push("chrome-headless-shell.exe");
+            }
+        }
+        path
+    }
+}
+
+/// What to resolve against the Chrome for Testing manifests.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) enum VersionSpec {
+    /// The latest version known to be good on a channel, e.g. `stable`.
+    Channel(Channel),
+    /// The latest version of a milestone, e.g. `120`.
+    Milestone(u32),
+    /// An exact version, e.g. `120.0.6099.109`.
+    Exact(String),
+}
+
+impl VersionSpec {
+    // the manifest which has the information needed to resolve this
+    pub(crate) fn manifest_name(&self) -> &'static str {
+        match self {
+            Self::Channel(_) => "last-known-good-versions-with-downloads.json",
+            Self::Milestone(_) => "latest-versions-per-milestone-with-downloads.json",
+            Self::Exact(_) => "known-good-versions-with-downloads.json",
+        }
+    }
+}
+
+impl FromStr for VersionSpec {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Fallible<Self> {
+        if let Ok(channel) = s.parse() {
+            return Ok(Self::Channel(channel));
+        }
+        if let Ok(milestone) = s.parse() {
+            return Ok(Self::Milestone(milestone));
+        }
+        if s.split('.').all(|part| part.parse::<u32>().is_ok()) {
+            return Ok(Self::Exact(s.to_string()));
+        }
+        Err(format_err!(
+            "Expected a channel, milestone or version, got: {}",
+            s
+        ))
+    }
+}
+
+/// The outcome of resolving a [`VersionSpec`](enum.VersionSpec.html) for one artifact.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub(crate) struct Resolved {
+    pub version: String,
+    pub url: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct Download {
+    platform: String,
+    url: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct VersionEntry {
+    version: String,
+    // not every version has downloads for every artifact, e.g. chrome-headless-shell was only
+    // published from M120 onwards
+    #[serde(default)]
+    downloads: HashMap<String, Vec<Download>>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LastKnownGoodVersions {
+    channels: HashMap<String, VersionEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct LatestVersionsPerMilestone {
+    milestones: HashMap<String, VersionEntry>,
+}
+
+#[derive(Deserialize, Debug)]
+struct KnownGoodVersions {
+    versions: Vec<VersionEntry>,
+}
+
+/// Finds the version `spec` refers to and where to download `artifact` of it for the current
+/// platform. `manifest` is the contents of the JSON endpoint named by `spec.manifest_name()`.
+pub(crate) fn resolve(
+    manifest: &str,
+    spec: &VersionSpec,
+    artifact: Artifact,
+) -> Fallible<Resolved> {
+    resolve_for(manifest, spec, artifact, PLATFORM)
+}
+
+fn resolve_for(
+    manifest: &str,
+    spec: &VersionSpec,
+    artifact: Artifact,
+    platform: &str,
+) -> Fallible<Resolved> {
+    let entry = match spec {
+        VersionSpec::Channel(channel) => serde_json::from_str::<LastKnownGoodVersions>(manifest)?
+            .channels
+            .remove(channel.manifest_key())
+            .ok_or_else(|| format_err!("No known good version on channel {:?}", channel))?,
+        VersionSpec::Milestone(milestone) => {
+            serde_json::from_str::<LatestVersionsPerMilestone>(manifest)?
+                .milestones
+                .remove(&milestone.to_string())
+                .ok_or_else(|| format_err!("No known good version for milestone {}", milestone))?
+        }
+        VersionSpec::Exact(version) => serde_json::from_str::<KnownGoodVersions>(manifest)?
+            .versions
+            .into_iter()
+            .find(|entry| &entry.version == version)
+            .ok_or_else(|| format_err!("{} is not a known good version", version))?,
+    };
+
+    let url = entry
+        .downloads
+        .get(artifact.manifest_key())
+        .and_then(|downloads| {
+            downloads
+                .iter()
+                .find(|download| download.platform == platform)
+        })
+        .map(|download| download.url.clone())
+        .ok_or_else(|| {
+            format_err!(
+                "No {} download of {} for {}",
+                artifact.manifest_key(),
+                entry.version,
+                platform
+            )
+        })?;
+
+    Ok(Resolved {
+        version: entry.version,
+        url,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LAST_KNOWN_GOOD: &str = include_str!(
+        "../../../tests/fetcher_fixtures/last-known-good-versions-with-downloads.json"
+    );
+    const PER_MILESTONE: &str = include_str!(
+        "../../../tests/fetcher_fixtures/latest-versions-per-milestone-with-downloads.json"
+    );
+    const KNOWN_GOOD: &str =
+        include_str!("../../../tests/fetcher_fixtures/known-good-versions-with-downloads.json");
+
+    #[test]
+    fn parses_version_specs() {
+        assert_eq!(
+            "stable".parse::<VersionSpec>().unwrap(),
+            VersionSpec::Channel(Channel::Stable)
+        );
+        assert_eq!(
+            "Beta".parse::<VersionSpec>().unwrap(),
+            VersionSpec::Channel(Channel::Beta)
+        );
+        assert_eq!(
+            "120".parse::<VersionSpec>().unwrap(),
+            VersionSpec::Milestone(120)
+        );
+        assert_eq!(
+            "120.0.6099.109".parse::<VersionSpec>().unwrap(),
+            VersionSpec::Exact("120.0.6099.109".to_string())
+        );
+        assert!("latest".parse::<VersionSpec>().is_err());
+        assert!("120.0.x".parse::<VersionSpec>().is_err());
+    }
+
+    #[test]
+    fn resolves_channels() {
+        let stable = resolve_for(
+            LAST_KNOWN_GOOD,
+            &VersionSpec::Channel(Channel::Stable),
+            Artifact::Chrome,
+            "linux64",
+        )
+        .unwrap();
+        assert_eq!(stable.version, "120.0.6099.109");
+        assert_eq!(
+            stable.url,
+            "https://edgedl.me.gvt1.com/edgedl/chrome/chrome-for-testing/120.0.6099.109/linux64/chrome-linux64.zip"
+        );
+
+        let beta = resolve_for(
+            LAST_KNOWN_GOOD,
+            &VersionSpec::Channel(Channel::Beta),
+            Artifact::HeadlessShell,
+            "mac-arm64",
+        )
+        .unwrap();
+        assert_eq!(beta.version, "121.0.6167.16");
+        assert_eq!(
+            beta.url,
+            "https://edgedl.me.gvt1.com/edgedl/chrome/chrome-for-testing/121.0.6167.16/mac-arm64/chrome-headless-shell-mac-arm64.zip"
+        );
+    }
+
+    #[test]
+    fn resolves_milestones() {
+        let resolved = resolve_for(
+            PER_MILESTONE,
+            &VersionSpec::Milestone(119),
+            Artifact::Chrome,
+            "win64",
+        )
+        .unwrap();
+        assert_eq!(resolved.version, "119.0.6045.105");
+        assert!(resolved
+            .url
+            .ends_with("/119.0.6045.105/win64/chrome-win64.zip"));
+
+        assert!(resolve_for(
+            PER_MILESTONE,
+            &VersionSpec::Milestone(42),
+            Artifact::Chrome,
+            "win64",
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn resolves_exact_versions() {
+        let resolved = resolve_for(
+            KNOWN_GOOD,
+            &VersionSpec::Exact("120.0.6099.109".to_string()),
+            Artifact::HeadlessShell,
+            "linux64",
+        )
+        .unwrap();
+        assert_eq!(resolved.version, "120.0.6099.109");
+        assert!(resolved
+            .url
+            .ends_with("/120.0.6099.109/linux64/chrome-headless-shell-linux64.zip"));
+
+        assert!(resolve_for(
+            KNOWN_GOOD,
+            &VersionSpec::Exact("1.2.3.4".to_string()),
+            Artifact::Chrome,
+            "linux64",
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn reports_missing_artifacts() {
+        // chrome-headless-shell wasn't published before M120
+        let error = resolve_for(
+            KNOWN_GOOD,
+            &VersionSpec::Exact("113.0.5672.0".to_string()),
+            Artifact::HeadlessShell,
+            "linux64",
+        )
+        .unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "No chrome-headless-shell download of 113.0.5672.0 for linux64"
+        );
+    }
+}
@@ -0,0 +1,1200 @@
+use std::{
+    cmp::Ordering,
+    collections::HashMap,
+    fs::{self, File, OpenOptions},
+    io::{self, BufWriter, Read, Write},
+    path::{Path, PathBuf},
+    str::FromStr,
+    sync::Arc,
+};
+
+use directories::ProjectDirs;
+use failure::{format_err, Fail, Fallible};
+use fs2::FileExt;
+use log::*;
+use sha2::{Digest, Sha256};
+use ureq;
+use zip;
+
+pub use self::chrome_for_testing::{Artifact, Channel};
+use self::chrome_for_testing::{VersionSpec, DEFAULT_MANIFEST_BASE_URL};
+
+mod chrome_for_testing;
+
+pub const CUR_REV: &str = "634997";
+
+const APP_NAME: &str = "headless-chrome";
+const LOCK_FILE_NAME: &str = ".install.lock";
+const DEFAULT_BASE_URL: &str = "https://storage.googleapis.com/chromium-browser-snapshots";
+
+#[cfg(target_os = "linux")]
+const PLATFORM: &str = "linux";
+#[cfg(target_os = "macos")]
+const PLATFORM: &str = "mac";
+#[cfg(windows)]
+const PLATFORM: &str = "win";
+
+/// Called while a revision is being downloaded with the number of bytes received so far and, if
+/// the server told us, the total size of the archive in bytes.
+pub type ProgressCallback = Arc<dyn Fn(u64, Option<u64>) + Send + Sync>;
+
+#[derive(Debug, Fail)]
+#[fail(
+    display = "Checksum mismatch for {}: expected {}, got {}",
+    archive, expected, actual
+)]
+pub struct ChecksumMismatch {
+    pub archive: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+#[derive(Clone)]
+pub struct FetcherOptions {
+    /// The desired chrome revision.
+    ///
+    /// defaults to CUR_REV
+    revision: String,
+
+    /// The prefered installation directory. If not None we will look here first
+    /// for existing installs.
+    ///
+    /// defaults to None
+    install_dir: Option<PathBuf>,
+
+    /// Allow headless_chrome to download and install the desired revision if not found.
+    ///
+    /// defaults to true
+    allow_download: bool,
+
+    /// Allow looking in the standard installation directories for existing installs.
+    ///
+    /// defaults to true
+    allow_standard_dirs: bool,
+
+    /// Known SHA-256 checksums of the downloaded archives, keyed by `{PLATFORM}-{REVISION}`.
+    /// Downloads of a revision with a known checksum are verified before being installed.
+    ///
+    /// defaults to empty
+    checksums: HashMap<String, String>,
+
+    /// Refuse to install a revision for which no checksum is known.
+    ///
+    /// defaults to false
+    require_checksum: bool,
+
+    /// Called as the download of a revision progresses.
+    ///
+    /// defaults to None
+    progress_callback: Option<ProgressCallback>,
+
+    /// Where revisions are downloaded from. Archives are expected at
+    /// `{base_url}/{Linux_x64|Mac|Win_x64}/{REVISION}/{chrome-linux|chrome-mac|chrome-win}.zip`,
+    /// the same layout as the Chromium snapshot bucket. Besides HTTP(S) URLs, this can be a
+    /// `file://` URL or a plain path to a local directory with that layout.
+    ///
+    /// defaults to the Chromium snapshot bucket
+    base_url: String,
+
+    /// Fetch a [Chrome for Testing](https://github.com/GoogleChromeLabs/chrome-for-testing)
+    /// build instead of a Chromium snapshot. Takes precedence over `revision`.
+    ///
+    /// defaults to None
+    chrome_for_testing: Option<VersionSpec>,
+
+    /// Which Chrome for Testing binary to fetch.
+    ///
+    /// defaults to Artifact::Chrome
+    artifact: Artifact,
+
+    /// Where the Chrome for Testing JSON manifests are read from. Like `base_url`, this can
+    /// also be a `file://` URL or a plain path.
+    ///
+    /// defaults to the official Chrome for Testing endpoints
+    manifest_base_url: String,
+}
+
+impl Default for FetcherOptions {
+    fn default() -> Self {
+        Self {
+            revision: CUR_REV.into(),
+            install_dir: None,
+            allow_download: true,
+            allow_standard_dirs: true,
+            checksums: HashMap::new(),
+            require_checksum: false,
+            progress_callback: None,
+            base_url: DEFAULT_BASE_URL.into(),
+            chrome_for_testing: None,
+            artifact: Artifact::default(),
+            manifest_base_url: DEFAULT_MANIFEST_BASE_URL.into(),
+        }
+    }
+}
+
+impl FetcherOptions {
+    pub fn with_revision<S: Into<String>>(mut self, revision: S) -> Self {
+        self.revision = revision.into();
+        self.chrome_for_testing = None;
+        self
+    }
+
+    /// Fetches the Chrome for Testing build `version` resolves to, which can be a channel
+    /// (`"stable"`, `"beta"`, `"dev"` or `"canary"`), a milestone (`"120"`) or an exact version
+    /// (`"120.0.6099.109"`).
+    pub fn with_version<S: AsRef<str>>(mut self, version: S) -> Fallible<Self> {
+        self.chrome_for_testing = Some(version.as_ref().parse()?);
+        Ok(self)
+    }
+
+    /// Fetches the latest Chrome for Testing build known to be good on `channel`.
+    pub fn with_channel(mut self, channel: Channel) -> Self {
+        self.chrome_for_testing = Some(VersionSpec::Channel(channel));
+        self
+    }
+
+    pub fn with_artifact(mut self, artifact: Artifact) -> Self {
+        self.artifact = artifact;
+        self
+    }
+
+    /// Reads the Chrome for Testing manifests (`last-known-good-versions-with-downloads.json`
+    /// etc.) from a mirror instead.
+    pub fn with_manifest_base_url<S: Into<String>>(mut self, manifest_base_url: S) -> Self {
+        self.manifest_base_url = manifest_base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+
+    pub fn with_install_dir<P: Into<PathBuf>>(mut self, install_dir: Option<P>) -> Self {
+        match install_dir {
+            Some(dir) => self.install_dir = Some(dir.into()),
+            None => self.install_dir = None,
+        }
+        self
+    }
+
+    pub fn with_allow_download(mut self, allow_download: bool) -> Self {
+        self.allow_download = allow_download;
+        self
+    }
+
+    pub fn with_allow_standard_dirs(mut self, allow_standard_dirs: bool) -> Self {
+        self.allow_standard_dirs = allow_standard_dirs;
+        self
+    }
+
+    /// Registers the expected SHA-256 checksum (hex encoded) of the archive for `revision` on
+    /// the current platform.
+    pub fn with_checksum<R: AsRef<str>, S: Into<String>>(mut self, revision: R, sha256: S) -> Self {
+        self.checksums.insert(
+            format!("{}-{}", PLATFORM, revision.as_ref()),
+            sha256.into().to_lowercase(),
+        );
+        self
+    }
+
+    /// Registers all checksums listed in a manifest, in the format produced by `sha256sum`:
+    ///
+    /// ```text
+    /// 5f0c...e1a2  linux-634997.zip
+    /// 9b3d...77c0  mac-634997.zip
+    /// ```
+    pub fn with_checksum_manifest(mut self, manifest: &str) -> Fallible<Self> {
+        self.checksums.extend(parse_checksum_manifest(manifest)?);
+        Ok(self)
+    }
+
+    pub fn with_require_checksum(mut self, require_checksum: bool) -> Self {
+        self.require_checksum = require_checksum;
+        self
+    }
+
+    /// Sets a callback which receives the number of bytes downloaded so far and the total
+    /// size of the archive, if known.
+    pub fn with_progress_callback<F>(mut self, callback: F) -> Self
+    where
+        F: Fn(u64, Option<u64>) + Send + Sync + 'static,
+    {
+        self.progress_callback = Some(Arc::new(callback));
+        self
+    }
+
+    /// Downloads revisions from a mirror of the Chromium snapshot bucket instead, e.g.
+    /// `https://mirror.internal/chromium-browser-snapshots`, `file:///srv/chromium` or
+    /// `/srv/chromium`.
+    ///
+    /// Chrome for Testing builds are then expected at
+    /// `{base_url}/{VERSION}/{linux64|mac-x64|...}/{ARCHIVE}.zip`, the layout of the Chrome for
+    /// Testing bucket.
+    pub fn with_base_url<S: Into<String>>(mut self, base_url: S) -> Self {
+        self.base_url = base_url.into().trim_end_matches('/').to_string();
+        self
+    }
+}
+
+/// A revision of Chromium found in one of the installation directories.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Installation {
+    pub revision: String,
+    /// The `{PLATFORM}-{REVISION}` directory the revision was unpacked into.
+    pub path: PathBuf,
+}
+
+// held while installing into or pruning an installation directory, so that several processes
+// (e.g. parallel `cargo test` runs) sharing that directory don't trample over each other
+struct InstallLock(File);
+
+impl InstallLock {
+    fn acquire(dir: &Path) -> Fallible<Self> {
+        fs::create_dir_all(dir)?;
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .open(dir.join(LOCK_FILE_NAME))?;
+        if file.try_lock_exclusive().is_err() {
+            info!(
+                "Waiting for another process to finish with {}",
+                dir.display()
+            );
+            file.lock_exclusive()?;
+        }
+        Ok(Self(file))
+    }
+}
+
+impl Drop for InstallLock {
+    fn drop(&mut self) {
+        if self.0.unlock().is_err() {
+            warn!("Failed to release installation lock");
+        }
+    }
+}
+
+// where an archive is fetched from
+enum ArchiveSource {
+    Http(String),
+    Local(PathBuf),
+}
+
+impl ArchiveSource {
+    fn new(base_url: &str, relative_url: &str) -> Self {
+        Self::at(&format!("{}/{}", base_url, relative_url))
+    }
+
+    // `location` is an HTTP(S) URL, a `file://` URL or a plain path
+    fn at(location: &str) -> Self {
+        if location.starts_with("http://") || location.starts_with("https://") {
+            Self::Http(location.to_string())
+        } else if location.starts_with("file://") {
+            Self::Local(PathBuf::from(&location["file://".len()..]))
+        } else {
+            Self::Local(PathBuf::from(location))
+        }
+    }
+
+    fn read_to_string(&self) -> Fallible<String> {
+        match self {
+            Self::Http(url) => {
+                let resp = ureq::get(url).call();
+                if resp.error() {
+                    return Err(format_err!(
+                        "Fetching {} failed: {} {}",
+                        url,
+                        resp.status(),
+                        resp.status_text()
+                    ));
+                }
+                Ok(resp.into_string()?)
+            }
+            Self::Local(path) => fs::read_to_string(path)
+                .map_err(|err| format_err!("Could not read {}: {}", path.display(), err)),
+        }
+    }
+}
+
+// what is going to be installed, once the options have been resolved
+struct Target {
+    // the installation directory is named `{PLATFORM}-{key}`
+    key: String,
+    source: ArchiveSource,
+}
+
+#[derive(Default)]
+pub struct Fetcher {
+    options: FetcherOptions,
+}
+
+impl Fetcher {
+    pub fn new(options: FetcherOptions) -> Fallible<Self> {
+        Ok(Self { options })
+    }
+
+    // look for good existing installation, if none exists then download and install
+    pub fn fetch(&self) -> Fallible<PathBuf> {
+        if let Some(key) = self.known_key() {
+            if let Ok(chrome_path) = self.chrome_path(&key) {
+                // we found it!
+                return Ok(chrome_path);
+            }
+        }
+
+        if self.options.allow_download {
+            let target = self.target()?;
+            let _lock = InstallLock::acquire(&self.install_root()?)?;
+
+            // we might already have what a channel resolved to, or another process might have
+            // installed it while we were waiting for the lock
+            if let Ok(chrome_path) = self.chrome_path(&target.key) {
+                return Ok(chrome_path);
+            }
+
+            let zip_path = self.download(&target)?;
+
+            self.unzip(zip_path)?;
+
+            // look again
+            return self.chrome_path(&target.key);
+        }
+
+        // couldn't find and not allowed to download
+        Err(format_err!("Could not fetch"))
+    }
+
+    /// Lists the revisions installed for the current platform, newest first.
+    pub fn list_installed(&self) -> Fallible<Vec<Installation>> {
+        let mut installations = Vec::new();
+        for root_dir in self.search_dirs()? {
+            let entries = match fs::read_dir(&root_dir) {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+            for entry in entries.filter_map(Result::ok) {
+                if !entry.path().is_dir() {
+                    continue;
+                }
+                // filename is formatted as `{PLATFORM}-{REVISION}`
+                let filename = entry.file_name();
+                let filename_parts = match filename.to_str() {
+                    Some(filename) => filename.splitn(2, '-').collect::<Vec<_>>(),
+                    None => continue,
+                };
+
+                if filename_parts.len() == 2 && filename_parts[0] == PLATFORM {
+                    installations.push(Installation {
+                        revision: filename_parts[1].to_string(),
+                        path: entry.path(),
+                    });
+                }
+            }
+        }
+
+        installations.sort_by(|a, b| compare_revisions(&b.revision, &a.revision));
+        Ok(installations)
+    }
+
+    /// Deletes all installed revisions except for the `keep_latest` newest ones and the revision
+    /// these options ask for. Returns the installations that were removed.
+    pub fn prune(&self, keep_latest: usize) -> Fallible<Vec<Installation>> {
+        let keep = self.known_key();
+        let mut removed = Vec::new();
+        for installation in self.list_installed()?.into_iter().skip(keep_latest) {
+            if Some(&installation.revision) == keep.as_ref() {
+                continue;
+            }
+            let parent = installation
+                .path
+                .parent()
+                .ok_or_else(|| format_err!("{:?} has no parent directory", installation.path))?;
+            let _lock = InstallLock::acquire(parent)?;
+            info!("Removing {}", installation.path.display());
+            fs::remove_dir_all(&installation.path)?;
+            removed.push(installation);
+        }
+        Ok(removed)
+    }
+
+    // we want to look in install_dir first, then data dir
+    fn search_dirs(&self) -> Fallible<Vec<PathBuf>> {
+        let mut search_dirs = Vec::new();
+        if let Some(install_dir) = &self.options.install_dir {
+            search_dirs.push(install_dir.clone());
+        }
+        if self.options.allow_standard_dirs {
+            search_dirs.push(get_project_dirs()?.data_dir().to_path_buf());
+        }
+        Ok(search_dirs)
+    }
+
+    // the directory new revisions get installed into
+    fn install_root(&self) -> Fallible<PathBuf> {
+        if let Some(dir) = &self.options.install_dir {
+            // we have a preferred install location
+            Ok(dir.clone())
+        } else if self.options.allow_standard_dirs {
+            Ok(get_project_dirs()?.data_dir().to_path_buf())
+        } else {
+            // No preferred install dir and not allowed to use standard dirs.
+            // Not likely for someone to try and do this on purpose.
+            Err(format_err!("No allowed installation directory"))
+        }
+    }
+
+    // the installation the options ask for, if we can tell without resolving a channel or
+    // milestone first
+    fn known_key(&self) -> Option<String> {
+        match &self.options.chrome_for_testing {
+            None => Some(self.options.revision.clone()),
+            Some(VersionSpec::Exact(version)) => Some(self.options.artifact.install_key(version)),
+            Some(_) => None,
+        }
+    }
+
+    // work out what to download, and from where
+    fn target(&self) -> Fallible<Target> {
+        let spec = match &self.options.chrome_for_testing {
+            Some(spec) => spec,
+            None => {
+                return Ok(Target {
+                    key: self.options.revision.clone(),
+                    source: ArchiveSource::new(
+                        &self.options.base_url,
+                        &archive_url_path(&self.options.revision)?,
+                    ),
+                })
+            }
+        };
+
+        let manifest = ArchiveSource::new(&self.options.manifest_base_url, spec.manifest_name())
+            .read_to_string()?;
+        let artifact = self.options.artifact;
+        let resolved = chrome_for_testing::resolve(&manifest, spec, artifact)?;
+        info!(
+            "Resolved {:?} to Chrome for Testing {}",
+            spec, resolved.version
+        );
+
+        let source = if self.options.base_url == DEFAULT_BASE_URL {
+            ArchiveSource::at(&resolved.url)
+        } else {
+            ArchiveSource::new(
+                &self.options.base_url,
+                &format!(
+                    "{}/{}/{}.zip",
+                    resolved.version,
+                    chrome_for_testing::PLATFORM,
+                    artifact.archive_name()
+                ),
+            )
+        };
+
+        Ok(Target {
+            key: artifact.install_key(&resolved.version),
+            source,
+        })
+    }
+
+    // Look for an installation directory matching key
+    fn base_path(&self, key: &str) -> Fallible<PathBuf> {
+        self.list_installed()?
+            .into_iter()
+            .find(|installation| installation.revision == key)
+            .map(|installation| installation.path)
+            .ok_or_else(|| format_err!("Could not find an existing revision"))
+    }
+
+    // find full path to chrome executable from base_path
+    fn chrome_path(&self, key: &str) -> Fallible<PathBuf> {
+        let mut path = self.base_path(key)?;
+        if self.options.chrome_for_testing.is_some() {
+            path.push(self.options.artifact.executable());
+            return existing_executable(path);
+        }
+        path.push(archive_name(&self.options.revision)?);
+
+        #[cfg(target_os = "linux")]
+        {
+            path.push("chrome");
+        }
+        #[cfg(target_os = "macos")]
+        {
+            path.push("Chromium.app");
+            path.push("Contents");
+            path.push("MacOS");
+            path.push("Chromium");
+        }
+        #[cfg(windows)]
+        {
+            path.push("chrome.exe");
+        }
+
+        existing_executable(path)
+    }
+
+    // download a .zip of the revision we want, resuming a previous partial download if there is
+    // one, and verify it against its known checksum
+    fn download(&self, target: &Target) -> Fallible<PathBuf> {
+        let mut path = self.install_root()?;
+        path.push(format!("{}-{}.zip", PLATFORM, target.key));
+        // we need to create this directory in case it doesn't exist yet
+        fs::create_dir_all(
+            path.parent()
+                .ok_or_else(|| format_err!("Path {:?} does not have a parent directory", path))?,
+        )
+        .map_err(|_err| format_err!("Could not create directory at {:?}", path.parent()))?;
+
+        let key = format!("{}-{}", PLATFORM, target.key);
+        let expected_checksum = self.options.checksums.get(&key);
+        if expected_checksum.is_none() {
+            if self.options.require_checksum {
+                return Err(format_err!("No known checksum for {}", key));
+            }
+            warn!(
+                "No known checksum for {}, the download will not be verified",
+                key
+            );
+        }
+
+        // the archive is only moved to its final location once it's complete and verified
+        let part_path = path.with_extension("zip.part");
+
+        let (mut reader, mut file, mut downloaded, total) = match &target.source {
+            ArchiveSource::Http(url) => {
+                info!("Chrome download url: {}", url);
+                match self.open_http(url, &part_path)? {
+                    Some(opened) => opened,
+                    None => {
+                        // whatever we have on disk can't be resumed, so start from scratch
+                        warn!("Server refused to resume download, starting over");
+                        fs::remove_file(&part_path)?;
+                        return self.download(target);
+                    }
+                }
+            }
+            ArchiveSource::Local(archive_path) => {
+                info!("Copying Chrome from {}", archive_path.display());
+                let archive = File::open(archive_path).map_err(|err| {
+                    format_err!("Could not open {}: {}", archive_path.display(), err)
+                })?;
+                let total = archive.metadata()?.len();
+                let reader: Box<dyn Read> = Box::new(archive);
+                (reader, File::create(&part_path)?, 0, Some(total))
+            }
+        };
+        if let Some(total) = total {
+            info!("Total size of download: {} MiB", total / 2_u64.pow(20));
+        }
+
+        let mut buffer = [0; 64 * 1024];
+        loop {
+            let read = reader.read(&mut buffer)?;
+            if read == 0 {
+                break;
+            }
+            file.write_all(&buffer[..read])?;
+            downloaded += read as u64;
+            if let Some(callback) = &self.options.progress_callback {
+                callback(downloaded, total);
+            }
+        }
+        file.sync_all()?;
+
+        if let Some(total) = total {
+            if downloaded < total {
+                // keep the partial file around so the next attempt can resume it
+                return Err(format_err!(
+                    "Download of {} was truncated ({} of {} bytes)",
+                    key,
+                    downloaded,
+                    total
+                ));
+            }
+        }
+
+        if let Some(expected) = expected_checksum {
+            let actual = sha256_of(&part_path)?;
+            if &actual != expected {
+                fs::remove_file(&part_path)?;
+                return Err(ChecksumMismatch {
+                    archive: key,
+                    expected: expected.clone(),
+                    actual,
+                }
+                .into());
+            }
+            debug!("Verified checksum of {}", key);
+        }
+
+        fs::rename(&part_path, &path)?;
+
+        Ok(path)
+    }
+
+    // requests `url`, resuming into `part_path` if a previous attempt left a partial download.
+    // returns None if the server can't resume from what's on disk
+    fn open_http(
+        &self,
+        url: &str,
+        part_path: &Path,
+    ) -> Fallible<Option<(Box<dyn Read>, File, u64, Option<u64>)>> {
+        let resume_from = fs::metadata(part_path).map(|m| m.len()).unwrap_or(0);
+
+        let mut request = ureq::get(url);
+        if resume_from > 0 {
+            info!("Resuming download of {} from byte {}", url, resume_from);
+            request.set("Range", &format!("bytes={}-", resume_from));
+        }
+        let resp = request.call();
+
+        if resp.status() == 416 && resume_from > 0 {
+            return Ok(None);
+        }
+        if resp.error() {
+            return Err(format_err!(
+                "Downloading {} failed: {} {}",
+                url,
+                resp.status(),
+                resp.status_text()
+            ));
+        }
+
+        let (file, downloaded) = if resp.status() == 206 {
+            let file = OpenOptions::new().append(true).open(part_path)?;
+            (file, resume_from)
+        } else {
+            info!("Creating file for download: {}", part_path.display());
+            (File::create(part_path)?, 0)
+        };
+        let total = match resp.header("Content-Length") {
+            Some(len) => Some(u64::from_str(len)? + downloaded),
+            None => None,
+        };
+
+        Ok(Some((
+            Box::new(resp.into_reader()),
+            file,
+            downloaded,
+            total,
+        )))
+    }
+
+    // unzip the downloaded file and do all the needed file manipulation
+    fn unzip<P: AsRef<Path>>(&self, zip_path: P) -> Fallible<PathBuf> {
+        let mut archive = zip::ZipArchive::new(File::open(zip_path.as_ref())?)?;
+
+        let mut extract_path: PathBuf = zip_path
+            .as_ref()
+            .parent()
+            .ok_or_else(|| format_err!("zip_path does not have a parent directory"))?
+            .to_path_buf();
+
+        let folder_name = zip_path
+            .as_ref()
+            .file_stem()
+            .ok_or_else(|| format_err!("zip_path does not have a file stem"))?;
+
+        extract_path.push(folder_name);
+
+        // extract into a temporary directory next to the final one, and only move it into place
+        // once everything has been written, so an interrupted unzip never looks like an install
+        let temp_dir =
+            tempfile::Builder::new()
+                .prefix(&format!(".{}", folder_name.to_string_lossy()))
+                .tempdir_in(extract_path.parent().ok_or_else(|| {
+                    format_err!("extract_path does not have a parent directory")
+                })?)?;
+
+        info!(
+            "Extracting (this can take a while): {}",
+            extract_path.display()
+        );
+
+        for i in 0..archive.len() {
+            let mut file = archive.by_index(i)?;
+            let mut out_path = temp_dir.path().to_path_buf();
+            match file.enclosed_name() {
+                Some(path) => out_path.push(path),
+                None => {
+                    return Err(format_err!(
+                        "Archive entry escapes the install directory: {}",
+                        file.name()
+                    ))
+                }
+            }
+
+            let comment = file.comment();
+            if !comment.is_empty() {
+                trace!("File {} comment: {}", i, comment);
+            }
+
+            if (&*file.name()).ends_with('/') {
+                trace!(
+                    "File {} extracted to \"{}\"",
+                    i,
+                    out_path.as_path().display()
+                );
+                fs::create_dir_all(&out_path)?;
+            } else {
+                trace!(
+                    "File {} extracted to \"{}\" ({} bytes)",
+                    i,
+                    out_path.as_path().display(),
+                    file.size()
+                );
+                if let Some(p) = out_path.parent() {
+                    if !p.exists() {
+                        fs::create_dir_all(&p).unwrap();
+                    }
+                }
+                let mut out_file = BufWriter::new(File::create(&out_path)?);
+                io::copy(&mut file, &mut out_file)?;
+            }
+            // Get and Set permissions
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+
+                if let Some(mode) = file.unix_mode() {
+                    fs::set_permissions(&out_path, fs::Permissions::from_mode(mode)).unwrap();
+                }
+            }
+        }
+
+        if extract_path.exists() {
+            // someone else finished installing this revision in the meantime
+            info!(
+                "{} already exists, discarding our copy",
+                extract_path.display()
+            );
+        } else {
+            // the `TempDir` finds nothing left to delete once it drops
+            fs::rename(temp_dir.path(), &extract_path)?;
+        }
+
+        info!("Cleaning up");
+        if fs::remove_file(&zip_path).is_err() {
+            info!("Failed to delete zip");
+            return Ok(extract_path);
+        }
+
+        Ok(extract_path)
+    }
+}
+
+fn existing_executable(path: PathBuf) -> Fallible<PathBuf> {
+    if !path.exists() {
+        return Err(format_err!(
+            "Chrome executable missing from install: {:?}",
+            path
+        ));
+    }
+
+    Ok(path)
+}
+
+// orders revisions numerically, component by component for dotted version numbers. anything
+// after an `_` (e.g. `120.0.6099.109_headless_shell`) only breaks ties
+fn compare_revisions(a: &str, b: &str) -> Ordering {
+    let parse = |revision: &str| {
+        let mut parts = revision.splitn(2, '_');
+        let version = parts
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(str::parse::<u64>)
+            .collect::<Result<Vec<_>, _>>();
+        (version, parts.next().unwrap_or_default().to_string())
+    };
+    match (parse(a), parse(b)) {
+        ((Ok(a), a_suffix), (Ok(b), b_suffix)) => a.cmp(&b).then(a_suffix.cmp(&b_suffix)),
+        _ => a.cmp(b),
+    }
+}
+
+fn sha256_of<P: AsRef<Path>>(path: P) -> Fallible<String> {
+    let mut file = File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buffer = [0; 64 * 1024];
+    loop {
+        let read = file.read(&mut buffer)?;
+        if read == 0 {
+            break;
+        }
+        hasher.input(&buffer[..read]);
+    }
+    Ok(format!("{:x}", hasher.result()))
+}
+
+// parses `sha256sum`-style lines of `<hex digest> <name>`, where name is `{PLATFORM}-{REVISION}`
+// optionally followed by `.zip`
+fn parse_checksum_manifest(manifest: &str) -> Fallible<HashMap<String, String>> {
+    let mut checksums = HashMap::new();
+    for line in manifest.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+        let mut parts = line.split_whitespace();
+        let (digest, name) = match (parts.next(), parts.next(), parts.next()) {
+            (Some(digest), Some(name), None) => (digest, name),
+            _ => return Err(format_err!("Malformed checksum manifest line: {}", line)),
+        };
+        if digest.len() != 64 || !digest.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(format_err!("Not a SHA-256 digest: {}", digest));
+        }
+        // `sha256sum -b` marks binary files with a leading '*'
+        let name = name.trim_start_matches('*').trim_end_matches(".zip");
+        checksums.insert(name.to_string(), digest.to_lowercase());
+    }
+    Ok(checksums)
+}
+
+fn get_project_dirs() -> Fallible<ProjectDirs> {
+    info!("Getting project dir");
+    match ProjectDirs::from("", "", APP_NAME) {
+        Some(dirs) => Ok(dirs),
+        None => Err(format_err!("Failed to retrieve project dirs")),
+    }
+}
+
+// path of the archive for `revision`, relative to the base URL
+fn archive_url_path<R>(revision: R) -> Fallible<String>
+where
+    R: AsRef<str>,
+{
+    #[cfg(target_os = "linux")]
+    {
+        Ok(format!(
+            "Linux_x64/{}/{}.zip",
+            revision.as_ref(),
+            archive_name(revision.as_ref())?
+        ))
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        Ok(format!(
+            "Mac/{}/{}.zip",
+            revision.as_ref(),
+            archive_name(revision.as_ref())?
+        ))
+    }
+
+    #[cfg(windows)]
+    {
+        Ok(format!(
+            "Win_x64/{}/{}.zip",
+            revision.as_ref(),
+            archive_name(revision.as_ref())?
+        ))
+    }
+}
+
+fn archive_name<R: AsRef<str>>(revision: R) -> Fallible<&'static str> {
+    #[cfg(target_os = "linux")]
+    {
+        drop(revision);
+
+        Ok("chrome-linux")
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        drop(revision);
+
+        Ok("chrome-mac")
+    }
+
+    #[cfg(windows)]
+    {
+        // Windows archive name changed at r591479.
+        if revision.as_ref().parse::<u32>()? > 591_479 {
+            Ok("chrome-win")
+        } else {
+            Ok("chrome-win32")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicU64, Ordering};
+
+    use super::*;
+
+    mod server {
+        include!("../../testing_utils/server.rs");
+    }
+
+    const TEST_REV: &str = "700000";
+
+    #[cfg(target_os = "linux")]
+    const EXECUTABLE: &str = "chrome-linux/chrome";
+    #[cfg(target_os = "macos")]
+    const EXECUTABLE: &str = "chrome-mac/Chromium.app/Contents/MacOS/Chromium";
+    #[cfg(windows)]
+    const EXECUTABLE: &str = "chrome-win/chrome.exe";
+
+    // a stand-in for a Chromium snapshot, which only contains the executable
+    fn fake_archive() -> Vec<u8> {
+        archive_containing(EXECUTABLE)
+    }
+
+    fn archive_containing(executable: &str) -> Vec<u8> {
+        let mut zip = zip::ZipWriter::new(io::Cursor::new(Vec::new()));
+        zip.start_file(executable, zip::write::FileOptions::default())
+            .unwrap();
+        zip.write_all(&[0x42; 4096]).unwrap();
+        zip.finish().unwrap().into_inner()
+    }
+
+    fn checksum(data: &[u8]) -> String {
+        format!("{:x}", Sha256::digest(data))
+    }
+
+    fn options_for(install_dir: &Path, base_url: String) -> FetcherOptions {
+        FetcherOptions::default()
+            .with_revision(TEST_REV)
+            .with_install_dir(Some(install_dir))
+            .with_allow_standard_dirs(false)
+            .with_base_url(base_url)
+    }
+
+    fn mirror_of(archive: Vec<u8>) -> server::Server {
+        let mut files = HashMap::new();
+        files.insert(format!("/{}", archive_url_path(TEST_REV).unwrap()), archive);
+        server::binary_server(files)
+    }
+
+    #[test]
+    fn fetches_from_http_mirror() {
+        let archive = fake_archive();
+        let server = mirror_of(archive.clone());
+        let install_dir = tempfile::tempdir().unwrap();
+
+        let progress = Arc::new(AtomicU64::new(0));
+        let progress_clone = Arc::clone(&progress);
+        let options = options_for(install_dir.path(), server.url())
+            .with_checksum(TEST_REV, checksum(&archive))
+            .with_progress_callback(move |downloaded, total| {
+                assert!(total.map_or(true, |total| downloaded <= total));
+                progress_clone.store(downloaded, Ordering::SeqCst);
+            });
+
+        let chrome_path = Fetcher::new(options).unwrap().fetch().unwrap();
+        assert!(chrome_path.exists());
+        assert_eq!(progress.load(Ordering::SeqCst), archive.len() as u64);
+    }
+
+    #[test]
+    fn resumes_partial_download() {
+        let archive = fake_archive();
+        let server = mirror_of(archive.clone());
+        let install_dir = tempfile::tempdir().unwrap();
+
+        let part_path = install_dir
+            .path()
+            .join(format!("{}-{}.zip.part", PLATFORM, TEST_REV));
+        fs::write(&part_path, &archive[..archive.len() / 2]).unwrap();
+
+        let options = options_for(install_dir.path(), server.url())
+            .with_checksum(TEST_REV, checksum(&archive));
+        let chrome_path = Fetcher::new(options).unwrap().fetch().unwrap();
+        assert!(chrome_path.exists());
+        assert!(!part_path.exists());
+    }
+
+    #[test]
+    fn refuses_archive_with_wrong_checksum() {
+        let server = mirror_of(fake_archive());
+        let install_dir = tempfile::tempdir().unwrap();
+
+        let options = options_for(install_dir.path(), server.url())
+            .with_checksum(TEST_REV, checksum(b"something else"));
+        let error = Fetcher::new(options).unwrap().fetch().unwrap_err();
+        assert!(error.downcast::<ChecksumMismatch>().is_ok());
+        assert!(!install_dir
+            .path()
+            .join(format!("{}-{}", PLATFORM, TEST_REV))
+            .exists());
+    }
+
+    #[test]
+    fn parallel_fetches_share_one_install() {
+        let server = mirror_of(fake_archive());
+        let install_dir = tempfile::tempdir().unwrap();
+
+        let handles: Vec<_> = (0..4)
+            .map(|_| {
+                let options = options_for(install_dir.path(), server.url());
+                std::thread::spawn(move || Fetcher::new(options).unwrap().fetch().unwrap())
+            })
+            .collect();
+        for handle in handles {
+            assert!(handle.join().unwrap().exists());
+        }
+        assert_eq!(
+            Fetcher::new(options_for(install_dir.path(), server.url()))
+                .unwrap()
+                .list_installed()
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[test]
+    fn lists_and_prunes_installed_revisions() {
+        let install_dir = tempfile::tempdir().unwrap();
+        for name in &["1000", "200", "99.0.1", "99.0.10", "300"] {
+            fs::create_dir(install_dir.path().join(format!("{}-{}", PLATFORM, name))).unwrap();
+        }
+        fs::create_dir(install_dir.path().join("other-5")).unwrap();
+        fs::write(install_dir.path().join(format!("{}-7.zip", PLATFORM)), b"").unwrap();
+
+        let fetcher = Fetcher::new(
+            options_for(install_dir.path(), DEFAULT_BASE_URL.to_string()).with_revision("200"),
+        )
+        .unwrap();
+        let revisions = |installations: Vec<Installation>| -> Vec<String> {
+            installations.into_iter().map(|i| i.revision).collect()
+        };
+
+        assert_eq!(
+            revisions(fetcher.list_installed().unwrap()),
+            vec!["1000", "300", "200", "99.0.10", "99.0.1"]
+        );
+        assert_eq!(
+            revisions(fetcher.prune(1).unwrap()),
+            vec!["300", "99.0.10", "99.0.1"]
+        );
+        assert_eq!(
+            revisions(fetcher.list_installed().unwrap()),
+            vec!["1000", "200"]
+        );
+    }
+
+    #[test]
+    fn fetches_from_local_directory() {
+        let mirror_dir = tempfile::tempdir().unwrap();
+        let mut archive_path = mirror_dir.path().to_path_buf();
+        archive_path.extend(archive_url_path(TEST_REV).unwrap().split('/'));
+        fs::create_dir_all(archive_path.parent().unwrap()).unwrap();
+        fs::write(&archive_path, fake_archive()).unwrap();
+
+        for base_url in &[
+            format!("file://{}", mirror_dir.path().display()),
+            mirror_dir.path().display().to_string(),
+        ] {
+            let install_dir = tempfile::tempdir().unwrap();
+            let options = options_for(install_dir.path(), base_url.clone());
+            let chrome_path = Fetcher::new(options).unwrap().fetch().unwrap();
+            assert!(chrome_path.exists());
+        }
+    }
+
+    #[test]
+    fn fetches_chrome_for_testing_channel() {
+        let version = "120.0.6099.109";
+        let artifact = Artifact::HeadlessShell;
+        let executable = artifact.executable();
+        let executable = executable
+            .iter()
+            .map(|part| part.to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/");
+
+        let archive_path = format!(
+            "/{}/{}/{}.zip",
+            version,
+            chrome_for_testing::PLATFORM,
+            artifact.archive_name()
+        );
+        let mut files = HashMap::new();
+        files.insert(archive_path.clone(), archive_containing(&executable));
+        let server = server::binary_server(files);
+
+        let manifest_dir = tempfile::tempdir().unwrap();
+        let manifest = serde_json::json!({
+            "timestamp": "2023-12-14T08:09:19.375Z",
+            "channels": {
+                "Stable": {
+                    "channel": "Stable",
+                    "version": version,
+                    "revision": "1217362",
+                    "downloads": {
+                        "chrome-headless-shell": [{
+                            "platform": chrome_for_testing::PLATFORM,
+                            "url": format!("{}{}", server.url(), archive_path),
+                        }]
+                    }
+                }
+            }
+        });
+        fs::write(
+            manifest_dir
+                .path()
+                .join("last-known-good-versions-with-downloads.json"),
+            manifest.to_string(),
+        )
+        .unwrap();
+
+        let install_dir = tempfile::tempdir().unwrap();
+        let options = FetcherOptions::default()
+            .with_channel(Channel::Stable)
+            .with_artifact(artifact)
+            .with_manifest_base_url(manifest_dir.path().display().to_string())
+            .with_install_dir(Some(install_dir.path()))
+            .with_allow_standard_dirs(false);
+        let chrome_path = Fetcher::new(options.clone()).unwrap().fetch().unwrap();
+        assert!(chrome_path.ends_with(&executable));
+
+        let installed = Fetcher::new(options).unwrap().list_installed().unwrap();
+        assert_eq!(installed.len(), 1);
+        assert_eq!(installed[0].revision, "120.0.6099.109_headless_shell");
+
+        // an exact version doesn't need the manifest to find an existing install
+        let options = FetcherOptions::default()
+            .with_version(version)
+            .unwrap()
+            .with_artifact(artifact)
+            .with_manifest_base_url("/nonexistent")
+            .with_install_dir(Some(install_dir.path()))
+            .with_allow_standard_dirs(false)
+            .with_allow_download(false);
+        assert_eq!(Fetcher::new(options).unwrap().fetch().unwrap(), chrome_path);
+    }
+
+    #[test]
+    fn parses_sha256sum_manifest() {
+        let manifest = "
+            # checksums for our mirror
+            9F86D081884C7D659A2FEAA0C55AD015A3BF4F1B2B0B822CD15D6C15B0F00A08  linux-634997.zip
+            2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae *mac-634997.zip
+        ";
+        let checksums = parse_checksum_manifest(manifest).unwrap();
+        assert_eq!(checksums.len(), 2);
+        assert_eq!(
+            checksums["linux-634997"],
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+        assert_eq!(
+            checksums["mac-634997"],
+            "2c26b46b68ffc68ff99b453c1d30413413422d706483bfa0f98a5e886266e7ae"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_manifest() {
+        assert!(parse_checksum_manifest("not-a-digest linux-634997.zip").is_err());
+        assert!(parse_checksum_manifest("linux-634997.zip").is_err());
+    }
+
+    #[test]
+    fn hashes_files() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        file.write_all(b"test").unwrap();
+        assert_eq!(
+            sha256_of(file.path()).unwrap(),
+            "9f86d081884c7d659a2feaa0c55ad015a3bf4f1b2b0b822cd15d6c15b0f00a08"
+        );
+    }
+}
@@ -15,14 +15,15 @@ use transport::Transport;
 use which::which;
 
 use crate::browser::context::Context;
-use crate::protocol::browser::methods::GetVersion;
 pub use crate::protocol::browser::methods::VersionInformationReturnObject;
+use crate::protocol::browser::methods::{GetVersion, GrantPermissions, ResetPermissions};
+use crate::protocol::browser::Permission;
 use crate::protocol::target::methods::{CreateTarget, SetDiscoverTargets};
 use crate::protocol::{self, Event};
 use crate::util;
 
 #[cfg(feature = "fetch")]
-pub use fetcher::FetcherOptions;
+pub use fetcher::{Artifact, Channel, ChecksumMismatch, Fetcher, FetcherOptions, Installation};
 
 pub mod context;
 #[cfg(feature = "fetch")]
@@ -67,7 +68,8 @@ pub mod transport;
 ///
 /// While the Chrome DevTools Protocl (CDTP) does define some methods in a
 /// ["Browser" domain](https://chromedevtools.github.io/devtools-protocol/tot/Browser)
-/// (such as for resizing the window in non-headless mode), we currently don't implement those.
+/// (such as for resizing the window in non-headless mode), we currently only implement a few of
+/// them, such as `get_version` and `grant_permissions`.
 pub struct Browser {
     process: Option<Process>,
     transport: Arc<Transport>,
@@ -262,6 +264,43 @@ impl Browser {
         self.call_method(GetVersion {})
     }
 
+    /// Grants the given permissions to `origin`, so that pages from that origin don't get stuck
+    /// waiting on a permission prompt (e.g. for notifications or geolocation).
+    ///
+    /// Any permissions not in the list are denied.
+    ///
+    /// ```rust
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// #
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::protocol::browser::Permission;
+    /// # let browser = Browser::default()?;
+    /// browser.grant_permissions(
+    ///     "https://example.com",
+    ///     &[Permission::Geolocation, Permission::Notifications],
+    /// )?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn grant_permissions(&self, origin: &str, permissions: &[Permission]) -> Fallible<()> {
+        self.call_method(GrantPermissions {
+            origin: Some(origin),
+            permissions,
+            browser_context_id: None,
+        })?;
+        Ok(())
+    }
+
+    /// Resets the permissions of all origins to their defaults.
+    pub fn reset_permissions(&self) -> Fallible<()> {
+        self.call_method(ResetPermissions {
+            browser_context_id: None,
+        })?;
+        Ok(())
+    }
+
     fn handle_browser_level_events(
         &self,
         events_rx: mpsc::Receiver<Event>,
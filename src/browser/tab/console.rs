@@ -0,0 +1,361 @@
+use std::fmt;
+use std::sync::{Arc, Mutex, Weak};
+
+use failure::Fallible;
+
+use crate::browser::tab::{SyncSendEvent, Tab};
+use crate::protocol::runtime::events::{ConsoleAPICalledParams, ConsoleApiType};
+use crate::protocol::runtime::methods::{
+    CallFrame, ObjectPreview, RemoteObject, RemoteObjectSubtype, RemoteObjectType, StackTrace,
+};
+use crate::protocol::Event;
+
+/// A call a page made to `console.log` or one of the other `console` functions.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsoleMessage {
+    pub message_type: ConsoleApiType,
+    /// The arguments the way DevTools shows them, separated by spaces, with `%s`, `%d`, `%i`,
+    /// `%f`, `%o`, `%O` and `%c` in the first one replaced by the arguments that follow.
+    pub text: String,
+    /// Each argument on its own, e.g. `hello`, `42`, `[1, 2, 3]` or `{a: 1, b: 'two'}`.
+    pub values: Vec<String>,
+    pub args: Vec<RemoteObject>,
+    pub stack_trace: Option<StackTrace>,
+    /// Milliseconds since epoch.
+    pub timestamp: f64,
+}
+
+impl ConsoleMessage {
+    /// Where in the page's scripts the call was made.
+    pub fn location(&self) -> Option<&CallFrame> {
+        self.stack_trace
+            .as_ref()
+            .and_then(|stack_trace| stack_trace.call_frames.first())
+    }
+}
+
+impl From<ConsoleAPICalledParams> for ConsoleMessage {
+    fn from(params: ConsoleAPICalledParams) -> Self {
+        Self {
+            message_type: params.call_type,
+            text: format_message(&params.args),
+            values: params.args.iter().map(format_value).collect(),
+            args: params.args,
+            stack_trace: params.stack_trace,
+            timestamp: params.timestamp,
+        }
+    }
+}
+
+impl fmt::Display for ConsoleMessage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let message_type = format!("{:?}", self.message_type).to_lowercase();
+        write!(f, "[{}] {}", message_type, self.text)?;
+        if let Some(location) = self.location() {
+            write!(
+                f,
+                " ({}:{}:{})",
+                location.url,
+                location.line_number + 1,
+                location.column_number + 1
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// The console messages of a tab, collected since `Tab::collect_console` was called.
+///
+/// Its `Display` implementation prints a message per line, which makes for useful context when
+/// a test fails:
+///
+/// ```rust,no_run
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// # use headless_chrome::Browser;
+/// # let browser = Browser::default()?;
+/// let tab = browser.wait_for_initial_tab()?;
+/// let console = tab.collect_console()?;
+/// tab.navigate_to("https://example.com")?.wait_until_navigated()?;
+/// let heading = tab.find_element("h1")?.get_inner_text()?;
+/// assert_eq!(heading, "Example Domain", "page console:\n{}", console);
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct ConsoleBuffer {
+    messages: Arc<Mutex<Vec<ConsoleMessage>>>,
+}
+
+impl ConsoleBuffer {
+    /// Returns the messages collected so far, oldest first.
+    pub fn messages(&self) -> Vec<ConsoleMessage> {
+        self.messages.lock().unwrap().clone()
+    }
+
+    pub fn clear(&self) {
+        self.messages.lock().unwrap().clear();
+    }
+}
+
+impl fmt::Display for ConsoleBuffer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for message in self.messages.lock().unwrap().iter() {
+            writeln!(f, "{}", message)?;
+        }
+        Ok(())
+    }
+}
+
+// substitutes the arguments for the placeholders of the first one if it's a string, as
+// https://console.spec.whatwg.org/#formatter says, and joins whatever's left with spaces
+fn format_message(args: &[RemoteObject]) -> String {
+    let mut args = args.iter().peekable();
+    let mut parts = Vec::new();
+
+    if let Some(first) = args.peek() {
+        if first.object_type == RemoteObjectType::String {
+            let format = format_value(args.next().unwrap());
+            let mut text = String::new();
+            let mut chars = format.chars().peekable();
+            while let Some(character) = chars.next() {
+                if character != '%' {
+                    text.push(character);
+                    continue;
+                }
+                match chars.peek().copied() {
+                    Some('%') => {
+                        chars.next();
+                        text.push('%');
+                    }
+                    Some(specifier) if "sdifoOc".contains(specifier) => {
+                        chars.next();
+                        match args.next() {
+                            Some(_) if specifier == 'c' => {}
+                            Some(arg) if specifier == 'd' || specifier == 'i' => {
+                                let number = arg.value.as_ref().and_then(serde_json::Value::as_f64);
+                                text.push_str(&number.map_or_else(
+                                    || "NaN".to_string(),
+                                    |number| number.trunc().to_string(),
+                                ));
+                            }
+                            Some(arg) => text.push_str(&format_value(arg)),
+                            None => {
+                                text.push('%');
+                                text.push(specifier);
+                            }
+                        }
+                    }
+                    _ => text.push('%'),
+                }
+            }
+            parts.push(text);
+        }
+    }
+
+    parts.extend(args.map(format_value));
+    parts.join(" ")
+}
+
+// how DevTools shows a value: strings as they are, primitives as in JavaScript, arrays and
+// plain objects by their preview, and anything else by its description
+fn format_value(object: &RemoteObject) -> String {
+    if let Some(unserializable_value) = &object.unserializable_value {
+        return unserializable_value.clone();
+    }
+    match (&object.object_type, &object.value) {
+        (RemoteObjectType::Undefined, _) => "undefined".to_string(),
+        (RemoteObjectType::String, Some(serde_json::Value::String(string))) => string.clone(),
+        (_, Some(value)) => value.to_string(),
+        (RemoteObjectType::Object, None) => match (&object.subtype, &object.preview) {
+            (None | Some(RemoteObjectSubtype::Array), Some(preview)) => format_preview(preview),
+            (Some(RemoteObjectSubtype::Null), _) => "null".to_string(),
+            _ => describe(object),
+        },
+        _ => describe(object),
+    }
+}
+
+fn format_preview(preview: &ObjectPreview) -> String {
+    let is_array = preview.subtype.as_ref().map(String::as_str) == Some("array");
+    let mut entries: Vec<String> = preview
+        .properties
+        .iter()
+        .map(|property| {
+            let value = match (property.object_type.as_str(), &property.value) {
+                ("string", Some(value)) => format!("'{}'", value),
+                (_, Some(value)) => value.clone(),
+                (object_type, None) => object_type.to_string(),
+            };
+            if is_array {
+                value
+            } else {
+                format!("{}: {}", property.name, value)
+            }
+        })
+        .collect();
+    if preview.overflow {
+        entries.push("…".to_string());
+    }
+
+    if is_array {
+        format!("[{}]", entries.join(", "))
+    } else {
+        let class_name = match preview.description.as_ref().map(String::as_str) {
+            Some("Object") | None => "",
+            Some(description) => description,
+        };
+        let space = if class_name.is_empty() { "" } else { " " };
+        format!("{}{}{{{}}}", class_name, space, entries.join(", "))
+    }
+}
+
+fn describe(object: &RemoteObject) -> String {
+    object
+        .description
+        .clone()
+        .unwrap_or_else(|| format!("{:?}", object.object_type).to_lowercase())
+}
+
+impl Tab {
+    /// Calls `callback` with every message pages log to the console from now on, and with the
+    /// ones logged since the page loaded if the Runtime domain wasn't enabled yet.
+    ///
+    /// The callback is called from the tab's event handling thread, so it mustn't call methods
+    /// of the tab. It stays registered until it's removed with `remove_event_listener`.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::protocol::runtime::events::ConsoleApiType;
+    ///
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.on_console(|message| {
+    ///     if message.message_type == ConsoleApiType::Error {
+    ///         eprintln!("{}", message);
+    ///     }
+    /// })?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn on_console<F>(&self, callback: F) -> Fallible<Weak<SyncSendEvent>>
+    where
+        F: Fn(ConsoleMessage) + Send + Sync + 'static,
+    {
+        let listener = self.add_event_listener(Arc::new(move |event: &Event| {
+            if let Event::ConsoleAPICalled(event) = event {
+                callback(ConsoleMessage::from(event.params.clone()));
+            }
+        }))?;
+        self.enable_runtime()?;
+        Ok(listener)
+    }
+
+    /// Starts collecting the messages pages log to the console into a
+    /// [`ConsoleBuffer`](console/struct.ConsoleBuffer.html), see `on_console`.
+    pub fn collect_console(&self) -> Fallible<ConsoleBuffer> {
+        let buffer = ConsoleBuffer::default();
+        let messages = Arc::clone(&buffer.messages);
+        self.on_console(move |message| messages.lock().unwrap().push(message))?;
+        Ok(buffer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    fn remote_object(value: serde_json::Value) -> RemoteObject {
+        serde_json::from_value(value).unwrap()
+    }
+
+    #[test]
+    fn formats_values_like_devtools() {
+        let string = remote_object(json!({ "type": "string", "value": "hi" }));
+        let number = remote_object(json!({ "type": "number", "value": 4.5 }));
+        let undefined = remote_object(json!({ "type": "undefined" }));
+        let null = remote_object(json!({ "type": "object", "subtype": "null", "value": null }));
+        let nan = remote_object(json!({ "type": "number", "unserializableValue": "NaN" }));
+        let array = remote_object(json!({
+            "type": "object",
+            "subtype": "array",
+            "description": "Array(2)",
+            "preview": {
+                "type": "object",
+                "subtype": "array",
+                "description": "Array(2)",
+                "overflow": false,
+                "properties": [
+                    { "name": "0", "type": "number", "value": "1" },
+                    { "name": "1", "type": "string", "value": "two" }
+                ]
+            }
+        }));
+        let object = remote_object(json!({
+            "type": "object",
+            "className": "Object",
+            "description": "Object",
+            "preview": {
+                "type": "object",
+                "description": "Object",
+                "overflow": true,
+                "properties": [
+                    { "name": "a", "type": "number", "value": "1" },
+                    { "name": "b", "type": "object", "value": "Array(3)", "subtype": "array" }
+                ]
+            }
+        }));
+        let function = remote_object(json!({
+            "type": "function",
+            "className": "Function",
+            "description": "() => 1"
+        }));
+
+        assert_eq!(format_value(&string), "hi");
+        assert_eq!(format_value(&number), "4.5");
+        assert_eq!(format_value(&undefined), "undefined");
+        assert_eq!(format_value(&null), "null");
+        assert_eq!(format_value(&nan), "NaN");
+        assert_eq!(format_value(&array), "[1, 'two']");
+        assert_eq!(format_value(&object), "{a: 1, b: Array(3), …}");
+        assert_eq!(format_value(&function), "() => 1");
+    }
+
+    #[test]
+    fn substitutes_placeholders() {
+        let format = |args: serde_json::Value| {
+            let args: Vec<RemoteObject> = serde_json::from_value(args).unwrap();
+            format_message(&args)
+        };
+
+        assert_eq!(
+            format(json!([
+                { "type": "string", "value": "%s has %d%% of %i: %c%o" },
+                { "type": "string", "value": "Ferris" },
+                { "type": "number", "value": 99.9 },
+                { "type": "number", "value": 3 },
+                { "type": "string", "value": "color: red" },
+                { "type": "boolean", "value": true },
+                { "type": "undefined" }
+            ])),
+            "Ferris has 99% of 3: true undefined"
+        );
+        assert_eq!(
+            format(json!([
+                { "type": "number", "value": 1 },
+                { "type": "string", "value": "%s" }
+            ])),
+            "1 %s"
+        );
+        assert_eq!(
+            format(json!([{ "type": "string", "value": "100% %s" }])),
+            "100% %s"
+        );
+    }
+}
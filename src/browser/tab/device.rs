@@ -0,0 +1,336 @@
+//! Descriptions of phones, tablets and screens for `Tab::emulate`.
+//!
+//! The catalog follows the device list of Chrome's DevTools and Puppeteer. Devices not in it can
+//! be described with a `Device` literal:
+//!
+//! ```rust
+//! use headless_chrome::browser::tab::device::{Device, Viewport};
+//!
+//! let kiosk = Device {
+//!     name: "Kiosk",
+//!     user_agent: Some("Mozilla/5.0 (X11; Linux x86_64) KioskBrowser/1.0"),
+//!     viewport: Viewport {
+//!         width: 1080,
+//!         height: 1920,
+//!         device_scale_factor: 1.0,
+//!         is_mobile: false,
+//!         has_touch: true,
+//!         is_landscape: false,
+//!     },
+//! };
+//! ```
+
+/// The screen of a device.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Viewport {
+    /// In CSS pixels, i.e. what `window.innerWidth` says.
+    pub width: u32,
+    /// In CSS pixels, i.e. what `window.innerHeight` says.
+    pub height: u32,
+    /// How many physical pixels there are per CSS pixel, i.e. `window.devicePixelRatio`.
+    /// Screenshots are taken at this resolution.
+    pub device_scale_factor: f64,
+    /// Whether pages get a mobile browser's layout, taking the `<meta name="viewport">` tag
+    /// into account.
+    pub is_mobile: bool,
+    pub has_touch: bool,
+    pub is_landscape: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Device<'a> {
+    pub name: &'a str,
+    /// The user agent the device's browser sends, or `None` to keep Chrome's.
+    pub user_agent: Option<&'a str>,
+    pub viewport: Viewport,
+}
+
+impl<'a> Device<'a> {
+    /// Looks a device of the catalog up by its name, e.g. `"iPhone X"`, ignoring case.
+    pub fn find(name: &str) -> Option<&'static Device<'static>> {
+        DEVICES
+            .iter()
+            .find(|device| device.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Returns the same device turned on its side.
+    pub fn landscape(&self) -> Self {
+        self.with_orientation(true)
+    }
+
+    /// Returns the same device held upright.
+    pub fn portrait(&self) -> Self {
+        self.with_orientation(false)
+    }
+
+    fn with_orientation(&self, is_landscape: bool) -> Self {
+        let mut device = *self;
+        if device.viewport.is_landscape != is_landscape {
+            device.viewport.width = self.viewport.height;
+            device.viewport.height = self.viewport.width;
+            device.viewport.is_landscape = is_landscape;
+        }
+        device
+    }
+}
+
+const IOS_11_USER_AGENT: &str = "Mozilla/5.0 (iPhone; CPU iPhone OS 11_0 like Mac OS X) AppleWebKit/604.1.38 (KHTML, like Gecko) Version/11.0 Mobile/15A372 Safari/604.1";
+const IPAD_USER_AGENT: &str = "Mozilla/5.0 (iPad; CPU OS 11_0 like Mac OS X) AppleWebKit/604.1.34 (KHTML, like Gecko) Version/11.0 Mobile/15A5341f Safari/604.1";
+
+pub const IPHONE_SE: Device<'static> = Device {
+    name: "iPhone SE",
+    user_agent: Some("Mozilla/5.0 (iPhone; CPU iPhone OS 10_3_1 like Mac OS X) AppleWebKit/603.1.30 (KHTML, like Gecko) Version/10.0 Mobile/14E304 Safari/602.1"),
+    viewport: Viewport {
+        width: 320,
+        height: 568,
+        device_scale_factor: 2.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPHONE_8: Device<'static> = Device {
+    name: "iPhone 8",
+    user_agent: Some(IOS_11_USER_AGENT),
+    viewport: Viewport {
+        width: 375,
+        height: 667,
+        device_scale_factor: 2.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPHONE_8_PLUS: Device<'static> = Device {
+    name: "iPhone 8 Plus",
+    user_agent: Some(IOS_11_USER_AGENT),
+    viewport: Viewport {
+        width: 414,
+        height: 736,
+        device_scale_factor: 3.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPHONE_X: Device<'static> = Device {
+    name: "iPhone X",
+    user_agent: Some(IOS_11_USER_AGENT),
+    viewport: Viewport {
+        width: 375,
+        height: 812,
+        device_scale_factor: 3.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPHONE_11: Device<'static> = Device {
+    name: "iPhone 11",
+    user_agent: Some("Mozilla/5.0 (iPhone; CPU iPhone OS 13_7 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/13.1 Mobile/15E148 Safari/604.1"),
+    viewport: Viewport {
+        width: 414,
+        height: 828,
+        device_scale_factor: 2.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPHONE_12: Device<'static> = Device {
+    name: "iPhone 12",
+    user_agent: Some("Mozilla/5.0 (iPhone; CPU iPhone OS 14_4 like Mac OS X) AppleWebKit/605.1.15 (KHTML, like Gecko) Version/14.0.3 Mobile/15E148 Safari/604.1"),
+    viewport: Viewport {
+        width: 390,
+        height: 844,
+        device_scale_factor: 3.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPAD_MINI: Device<'static> = Device {
+    name: "iPad Mini",
+    user_agent: Some(IPAD_USER_AGENT),
+    viewport: Viewport {
+        width: 768,
+        height: 1024,
+        device_scale_factor: 2.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const IPAD_PRO_11: Device<'static> = Device {
+    name: "iPad Pro 11",
+    user_agent: Some(IPAD_USER_AGENT),
+    viewport: Viewport {
+        width: 834,
+        height: 1194,
+        device_scale_factor: 2.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const PIXEL_2: Device<'static> = Device {
+    name: "Pixel 2",
+    user_agent: Some("Mozilla/5.0 (Linux; Android 8.0; Pixel 2 Build/OPD3.170816.012) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/75.0.3765.0 Mobile Safari/537.36"),
+    viewport: Viewport {
+        width: 411,
+        height: 731,
+        device_scale_factor: 2.625,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const PIXEL_2_XL: Device<'static> = Device {
+    name: "Pixel 2 XL",
+    user_agent: Some("Mozilla/5.0 (Linux; Android 8.0.0; Pixel 2 XL Build/OPD1.170816.004) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/75.0.3765.0 Mobile Safari/537.36"),
+    viewport: Viewport {
+        width: 411,
+        height: 823,
+        device_scale_factor: 3.5,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const PIXEL_5: Device<'static> = Device {
+    name: "Pixel 5",
+    user_agent: Some("Mozilla/5.0 (Linux; Android 11; Pixel 5) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/90.0.4421.0 Mobile Safari/537.36"),
+    viewport: Viewport {
+        width: 393,
+        height: 851,
+        device_scale_factor: 2.75,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const GALAXY_S5: Device<'static> = Device {
+    name: "Galaxy S5",
+    user_agent: Some("Mozilla/5.0 (Linux; Android 5.0; SM-G900P Build/LRX21T) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/75.0.3765.0 Mobile Safari/537.36"),
+    viewport: Viewport {
+        width: 360,
+        height: 640,
+        device_scale_factor: 3.0,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const GALAXY_S9_PLUS: Device<'static> = Device {
+    name: "Galaxy S9+",
+    user_agent: Some("Mozilla/5.0 (Linux; Android 8.0.0; SM-G965U Build/R16NW) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/75.0.3765.0 Mobile Safari/537.36"),
+    viewport: Viewport {
+        width: 320,
+        height: 658,
+        device_scale_factor: 4.5,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+pub const GALAXY_TAB_S4: Device<'static> = Device {
+    name: "Galaxy Tab S4",
+    user_agent: Some("Mozilla/5.0 (Linux; Android 8.1.0; SM-T837A) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/75.0.3765.0 Safari/537.36"),
+    viewport: Viewport {
+        width: 712,
+        height: 1138,
+        device_scale_factor: 2.25,
+        is_mobile: true,
+        has_touch: true,
+        is_landscape: false,
+    },
+};
+
+/// A laptop with a retina screen, for high resolution screenshots of desktop layouts.
+pub const LAPTOP_HIDPI: Device<'static> = Device {
+    name: "Laptop with HiDPI screen",
+    user_agent: None,
+    viewport: Viewport {
+        width: 1440,
+        height: 900,
+        device_scale_factor: 2.0,
+        is_mobile: false,
+        has_touch: false,
+        is_landscape: true,
+    },
+};
+
+/// A desktop with a 1080p screen.
+pub const DESKTOP_1080P: Device<'static> = Device {
+    name: "Desktop 1080p",
+    user_agent: None,
+    viewport: Viewport {
+        width: 1920,
+        height: 1080,
+        device_scale_factor: 1.0,
+        is_mobile: false,
+        has_touch: false,
+        is_landscape: true,
+    },
+};
+
+/// All devices of the catalog.
+pub const DEVICES: &[Device<'static>] = &[
+    IPHONE_SE,
+    IPHONE_8,
+    IPHONE_8_PLUS,
+    IPHONE_X,
+    IPHONE_11,
+    IPHONE_12,
+    IPAD_MINI,
+    IPAD_PRO_11,
+    PIXEL_2,
+    PIXEL_2_XL,
+    PIXEL_5,
+    GALAXY_S5,
+    GALAXY_S9_PLUS,
+    GALAXY_TAB_S4,
+    LAPTOP_HIDPI,
+    DESKTOP_1080P,
+];
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_devices_by_name() {
+        assert_eq!(Device::find("iPhone X"), Some(&IPHONE_X));
+        assert_eq!(Device::find("galaxy s9+"), Some(&GALAXY_S9_PLUS));
+        assert_eq!(Device::find("Nokia 3310"), None);
+        for device in DEVICES {
+            assert_eq!(Device::find(device.name), Some(device));
+        }
+    }
+
+    #[test]
+    fn turns_devices_sideways() {
+        let landscape = IPHONE_X.landscape();
+        assert_eq!(
+            (landscape.viewport.width, landscape.viewport.height),
+            (812, 375)
+        );
+        assert!(landscape.viewport.is_landscape);
+        assert_eq!(landscape.landscape(), landscape);
+        assert_eq!(landscape.portrait(), IPHONE_X);
+        assert_eq!(LAPTOP_HIDPI.landscape(), LAPTOP_HIDPI);
+    }
+}
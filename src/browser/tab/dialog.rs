@@ -0,0 +1,125 @@
+use failure::Fallible;
+use log::*;
+
+use crate::browser::tab::Tab;
+use crate::protocol::page;
+
+/// An `alert`, `confirm`, `prompt` or `beforeunload` dialog a page opened.
+pub type Dialog = page::events::JavascriptDialogOpeningParams;
+
+/// How to close a dialog.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DialogResponse {
+    /// Whether to click OK (or Leave, for a `beforeunload` dialog) rather than Cancel.
+    pub accept: bool,
+    /// What to type into a prompt before accepting it. `None` leaves its default text.
+    pub prompt_text: Option<String>,
+}
+
+impl DialogResponse {
+    pub fn accept() -> Self {
+        Self {
+            accept: true,
+            prompt_text: None,
+        }
+    }
+
+    /// Answers a prompt with `text`.
+    pub fn accept_with(text: &str) -> Self {
+        Self {
+            accept: true,
+            prompt_text: Some(text.to_string()),
+        }
+    }
+
+    pub fn dismiss() -> Self {
+        Self {
+            accept: false,
+            prompt_text: None,
+        }
+    }
+}
+
+/// What a tab does with the dialogs its pages open, see `Tab::set_dialog_handler`.
+pub enum DialogHandler {
+    /// Clicks OK on everything, leaving prompts with their default text.
+    Accept,
+    /// Clicks Cancel on everything, so `confirm` returns `false` and `prompt` returns `null`.
+    Dismiss,
+    /// Asks a closure. It's called from the tab's event handling thread, so it mustn't call
+    /// methods of the tab, which would wait for that thread forever.
+    Callback(Box<dyn Fn(&Dialog) -> DialogResponse + Send + Sync>),
+}
+
+impl DialogHandler {
+    pub(crate) fn respond(&self, dialog: &Dialog) -> DialogResponse {
+        match self {
+            Self::Accept => DialogResponse::accept(),
+            Self::Dismiss => DialogResponse::dismiss(),
+            Self::Callback(callback) => callback(dialog),
+        }
+    }
+}
+
+impl Tab {
+    /// Closes the dialogs pages open as `handler` says.
+    ///
+    /// While a dialog is open, the page is frozen: nothing can be evaluated in it, and
+    /// `close_with_unload` doesn't close a page that asks whether to leave it. Without a handler,
+    /// dialogs stay open until they're closed with `handle_dialog`.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::browser::tab::dialog::{DialogHandler, DialogResponse};
+    /// use headless_chrome::protocol::page::DialogType;
+    ///
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.set_dialog_handler(DialogHandler::Callback(Box::new(|dialog| {
+    ///     match dialog.dialog_type {
+    ///         DialogType::Prompt => DialogResponse::accept_with("Ferris"),
+    ///         DialogType::Confirm => DialogResponse::dismiss(),
+    ///         _ => DialogResponse::accept(),
+    ///     }
+    /// })));
+    /// tab.evaluate("prompt('Who are you?')", false)?;
+    /// assert_eq!(tab.get_dialogs()[0].message, "Who are you?");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_dialog_handler(&self, handler: DialogHandler) -> &Self {
+        *self.dialog_handler.lock().unwrap() = Some(handler);
+        self
+    }
+
+    /// Goes back to leaving dialogs open.
+    pub fn remove_dialog_handler(&self) -> &Self {
+        *self.dialog_handler.lock().unwrap() = None;
+        self
+    }
+
+    /// Closes the dialog which is open, for when there's no dialog handler.
+    pub fn handle_dialog(&self, response: &DialogResponse) -> Fallible<&Self> {
+        debug!("Closing dialog: {:?}", response);
+        self.call_method(page::methods::HandleJavaScriptDialog {
+            accept: response.accept,
+            prompt_text: response.prompt_text.as_ref().map(String::as_str),
+        })?;
+        Ok(self)
+    }
+
+    /// Returns all dialogs that were opened in the tab so far, oldest first, whether they were
+    /// handled or not.
+    pub fn get_dialogs(&self) -> Vec<Dialog> {
+        self.dialogs.lock().unwrap().clone()
+    }
+
+    /// Forgets the dialogs `get_dialogs` returns.
+    pub fn clear_dialogs(&self) -> &Self {
+        self.dialogs.lock().unwrap().clear();
+        self
+    }
+}
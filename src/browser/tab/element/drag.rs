@@ -0,0 +1,112 @@
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+
+use failure::Fallible;
+use log::*;
+
+use super::Element;
+use crate::browser::tab::mouse::MouseButton;
+use crate::browser::tab::point::Point;
+use crate::protocol::input::{self, DragData};
+use crate::protocol::Event;
+use crate::util;
+
+impl<'a> Element<'a> {
+    /// Drags this element onto the middle of `target`, holding the left mouse button down.
+    ///
+    /// This works both for pages using HTML5 drag and drop (`draggable` elements and `drop`
+    /// events), which Chrome is made to hand over to `Input.dispatchDragEvent`, and for pages
+    /// which follow the mouse themselves, like most sliders and kanban boards, which get a
+    /// series of `mouseMoved` events along the way. Chrome versions without
+    /// `Input.setInterceptDrags` only get the mouse events.
+    pub fn drag_to(&self, target: &Element) -> Fallible<&Self> {
+        let start = self.wait_for_actionable()?;
+        let end = target.wait_for_actionable()?;
+        debug!("Dragging {:?} to {:?}", self, target);
+
+        let drag_data = Arc::new(Mutex::new(None));
+        let drag_data_clone = Arc::clone(&drag_data);
+        let listener = self
+            .parent
+            .add_event_listener(Arc::new(move |event: &Event| {
+                if let Event::DragIntercepted(event) = event {
+                    *drag_data_clone.lock().unwrap() = Some(event.params.data.clone());
+                }
+            }))?;
+
+        let intercepting = match self
+            .parent
+            .call_method(input::methods::SetInterceptDrags { enabled: true })
+        {
+            Ok(_) => true,
+            Err(error) => {
+                debug!("Can't intercept drags, only moving the mouse: {}", error);
+                false
+            }
+        };
+        let result = self.drag_between(
+            start,
+            end,
+            if intercepting { Some(&drag_data) } else { None },
+        );
+
+        // clean up whatever happened, but report an error of the drag itself first
+        let removed = self.parent.remove_event_listener(&listener);
+        let disabled = if intercepting {
+            self.parent
+                .call_method(input::methods::SetInterceptDrags { enabled: false })
+                .map(|_| ())
+        } else {
+            Ok(())
+        };
+        result?;
+        removed?;
+        disabled?;
+        Ok(self)
+    }
+
+    fn drag_between(
+        &self,
+        start: Point,
+        end: Point,
+        drag_data: Option<&Arc<Mutex<Option<DragData>>>>,
+    ) -> Fallible<()> {
+        let mouse = self.parent.mouse();
+        mouse.move_to(start)?;
+
+        let drag = || -> Fallible<()> {
+            mouse.down(MouseButton::Left)?;
+            mouse.move_by_steps(end, 10)?;
+
+            // an HTML5 drag is intercepted as soon as the mouse moves away from where it started,
+            // and the event announcing it may still be on its way
+            let intercepted = drag_data.and_then(|drag_data| {
+                util::Wait::new(Duration::from_millis(200), Duration::from_millis(20))
+                    .until(|| drag_data.lock().unwrap().take())
+                    .ok()
+            });
+            if let Some(data) = intercepted {
+                trace!("Drag was intercepted, dropping: {:?}", data);
+                for &event_type in &["dragEnter", "dragOver", "drop"] {
+                    self.parent.call_method(input::methods::DispatchDragEvent {
+                        event_type,
+                        x: end.x,
+                        y: end.y,
+                        data: &data,
+                        modifiers: None,
+                    })?;
+                }
+            }
+
+            Ok(())
+        };
+        let result = drag();
+
+        // the button counts as held down from the moment it's pressed, even if that failed, and
+        // later clicks would say so unless it's released
+        let released = mouse.up(MouseButton::Left);
+        result?;
+        released?;
+        Ok(())
+    }
+}
@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+
+use failure::{format_err, Fallible};
+use log::*;
+use serde_json::Value;
+
+use super::Element;
+use crate::browser::tab::Tab;
+
+impl<'a> Element<'a> {
+    /// Replaces the value of an `<input>`, `<textarea>` or content-editable element with `text`,
+    /// firing `input` and `change` events like a user would.
+    ///
+    /// Unlike `type_into`, this doesn't append to what's already there, and also works for inputs
+    /// without a keyboard-friendly format, like `<input type=date>` (given `"2019-12-31"`).
+    pub fn fill(&self, text: &str) -> Fallible<&Self> {
+        debug!("Filling {:?} with: {}", self, text);
+        self.focus()?;
+        self.query::<()>(
+            "function(value) {
+                if (this.isContentEditable) {
+                    this.textContent = value;
+                    this.dispatchEvent(new InputEvent('input', { bubbles: true }));
+                    return;
+                }
+                if (!this.matches('input, textarea'))
+                    throw new Error('Element is not an <input>, <textarea> or contenteditable');
+                const unfillable = ['checkbox', 'radio', 'file', 'button', 'submit', 'reset', 'image'];
+                if (this.matches('input') && unfillable.includes(this.type))
+                    throw new Error('Cannot fill <input type=' + this.type + '>');
+                if (this.disabled)
+                    throw new Error('Element is disabled');
+                if (this.readOnly)
+                    throw new Error('Element is read-only');
+
+                // using the setter from the prototype means frameworks which track the value,
+                // like React, notice it changed
+                const prototype = Object.getPrototypeOf(this);
+                Object.getOwnPropertyDescriptor(prototype, 'value').set.call(this, value);
+                if (this.value !== value)
+                    throw new Error('Value is malformed for <input type=' + this.type + '>');
+                this.dispatchEvent(new Event('input', { bubbles: true }));
+                this.dispatchEvent(new Event('change', { bubbles: true }));
+            }",
+            &[Value::String(text.to_string())],
+        )?;
+        Ok(self)
+    }
+
+    /// Empties an `<input>`, `<textarea>` or content-editable element, see `fill`.
+    pub fn clear(&self) -> Fallible<&Self> {
+        self.fill("")
+    }
+
+    /// Selects the options of a `<select>` whose value or label is one of `values`, deselecting
+    /// all others, and returns the values of the options now selected.
+    ///
+    /// Fails if any of `values` doesn't match an option. For a `<select>` which doesn't allow
+    /// multiple selections, the first match is selected.
+    pub fn select_option(&self, values: &[&str]) -> Fallible<Vec<String>> {
+        debug!("Selecting {:?} in {:?}", values, self);
+        self.query(
+            "function(wanted) {
+                if (!this.matches('select'))
+                    throw new Error('Element is not a <select>');
+                if (this.disabled)
+                    throw new Error('Element is disabled');
+                const options = [...this.options];
+                for (const value of wanted) {
+                    if (!options.some(option => option.value === value || option.label === value))
+                        throw new Error('No option has the value or label ' + value);
+                }
+                let selected = options.filter(
+                    option => wanted.includes(option.value) || wanted.includes(option.label));
+                if (!this.multiple)
+                    selected = selected.slice(0, 1);
+                for (const option of options)
+                    option.selected = selected.includes(option);
+                this.dispatchEvent(new Event('input', { bubbles: true }));
+                this.dispatchEvent(new Event('change', { bubbles: true }));
+                return selected.map(option => option.value);
+            }",
+            &[serde_json::to_value(values)?],
+        )
+    }
+
+    /// Checks a checkbox or radio button by clicking it, unless it's already checked.
+    pub fn check(&self) -> Fallible<&Self> {
+        self.set_checked(true)
+    }
+
+    /// Unchecks a checkbox by clicking it, unless it's already unchecked.
+    pub fn uncheck(&self) -> Fallible<&Self> {
+        self.set_checked(false)
+    }
+
+    /// Clicks a checkbox or radio button if it isn't already in the state wanted, and makes
+    /// sure that this got it there.
+    pub fn set_checked(&self, checked: bool) -> Fallible<&Self> {
+        if self.is_checked()? == checked {
+            return Ok(self);
+        }
+        self.click()?;
+        if self.is_checked()? != checked {
+            return Err(format_err!(
+                "Clicking {:?} didn't {} it",
+                self,
+                if checked { "check" } else { "uncheck" }
+            ));
+        }
+        Ok(self)
+    }
+
+    // fills in a form field of whatever kind this is with `value`
+    fn fill_field(&self, value: &str) -> Fallible<()> {
+        let kind: String = self.query(
+            "function() {
+                if (this.matches('select'))
+                    return 'select';
+                const checkable = 'input[type=checkbox], input[type=radio], '
+                    + '[role=checkbox], [role=radio], [role=switch]';
+                if (this.matches(checkable))
+                    return 'checkable';
+                return 'text';
+            }",
+            &[],
+        )?;
+        match kind.as_str() {
+            "select" => {
+                self.select_option(&[value])?;
+            }
+            "checkable" => {
+                let checked = value.parse().map_err(|_| {
+                    format_err!(
+                        "{:?} is a checkbox or radio button, expected true or false but got: {}",
+                        self,
+                        value
+                    )
+                })?;
+                self.set_checked(checked)?;
+            }
+            _ => {
+                self.fill(value)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Tab {
+    /// Fills in a form, given the values to fill in keyed by the selector of their fields.
+    ///
+    /// Each field is waited for and then filled in according to its kind: `<select>`s get the
+    /// option with that value or label selected, checkboxes and radio buttons are checked or
+    /// unchecked given `"true"` or `"false"`, and anything else is `fill`ed. The fields are filled
+    /// in no particular order.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use std::collections::HashMap;
+    /// # use headless_chrome::Browser;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// let mut values = HashMap::new();
+    /// values.insert("#name", "Ada Lovelace");
+    /// values.insert("#country", "United Kingdom");
+    /// values.insert("#newsletter", "true");
+    /// tab.fill_form(&values)?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn fill_form(&self, values: &HashMap<&str, &str>) -> Fallible<&Self> {
+        for (selector, value) in values {
+            self.wait_for_element(*selector)?.fill_field(value)?;
+        }
+        Ok(self)
+    }
+}
@@ -1,19 +1,27 @@
 use std::collections::HashMap;
 use std::fmt::Debug;
 
-use failure::{Fail, Fallible};
+use failure::{format_err, Error, Fail, Fallible};
 use log::*;
 
 use crate::browser::tab::point::Point;
+use crate::browser::tab::selector::Selector;
 use crate::browser::tab::NoElementFound;
 use crate::protocol::dom;
 use crate::protocol::page;
 use crate::protocol::runtime;
+use crate::protocol::types::JsInt;
+use crate::protocol::RemoteError;
+use crate::util;
 
 mod box_model;
+mod drag;
+mod form;
+mod state;
 
 use crate::protocol::runtime::methods::RemoteObjectType;
 pub use box_model::{BoxModel, ElementQuad};
+pub use state::ElementDetached;
 
 /// A handle to a [DOM Element](https://developer.mozilla.org/en-US/docs/Web/API/Element).
 ///
@@ -66,22 +74,243 @@ impl<'a> Element<'a> {
         })
     }
 
-    /// Moves the mouse to the middle of this element
+    /// Returns the first element below this one which matches `selector`.
+    pub fn find_element<S: Into<Selector>>(&self, selector: S) -> Fallible<Element<'a>> {
+        let selector = selector.into();
+        trace!(
+            "Looking up element below {:?} via selector: {}",
+            self,
+            selector
+        );
+
+        self.parent
+            .run_query_selector_on_node(self.current_node_id()?, selector)
+    }
+
+    /// Returns all elements below this one which match `selector`.
+    pub fn find_elements<S: Into<Selector>>(&self, selector: S) -> Fallible<Vec<Element<'a>>> {
+        let selector = selector.into();
+        trace!(
+            "Looking up elements below {:?} via selector: {}",
+            self,
+            selector
+        );
+
+        self.parent
+            .run_query_selector_all_on_node(self.current_node_id()?, selector)
+    }
+
+    pub fn wait_for_element<S: Into<Selector>>(&self, selector: S) -> Fallible<Element<'a>> {
+        let selector = selector.into();
+        debug!(
+            "Waiting for element below {:?} with selector: {}",
+            self, selector
+        );
+        util::Wait::with_timeout(*self.parent.default_timeout.read().unwrap()).strict_until(
+            || self.find_element(&selector),
+            Error::downcast::<NoElementFound>,
+        )
+    }
+
+    // `node_id` stops being valid once the document is fetched again, e.g. by another
+    // `Tab::find_element`, but the remote object stays the same
+    fn current_node_id(&self) -> Fallible<dom::NodeId> {
+        Ok(self
+            .parent
+            .call_method(dom::methods::RequestNode {
+                object_id: &self.remote_object_id,
+            })?
+            .node_id)
+    }
+
+    /// Moves the mouse to the middle of this element, once it's
+    /// [actionable](#method.wait_for_actionable).
     pub fn move_mouse_over(&self) -> Fallible<&Self> {
-        self.scroll_into_view()?;
-        let midpoint = self.get_midpoint()?;
+        let midpoint = self.wait_for_actionable()?;
         self.parent.move_mouse_to_point(midpoint)?;
         Ok(self)
     }
 
+    /// Clicks the middle of this element, once it's [actionable](#method.wait_for_actionable).
     pub fn click(&self) -> Fallible<&Self> {
-        self.scroll_into_view()?;
+        let midpoint = self.wait_for_actionable()?;
         debug!("Clicking element {:?}", &self);
-        let midpoint = self.get_midpoint()?;
         self.parent.click_point(midpoint)?;
         Ok(self)
     }
 
+    /// Double-clicks the middle of this element, once it's
+    /// [actionable](#method.wait_for_actionable).
+    pub fn double_click(&self) -> Fallible<&Self> {
+        let midpoint = self.wait_for_actionable()?;
+        debug!("Double-clicking element {:?}", &self);
+        self.parent.mouse().double_click(midpoint)?;
+        Ok(self)
+    }
+
+    /// Taps the middle of this element with a finger, once it's
+    /// [actionable](#method.wait_for_actionable). See `Tab::touchscreen`.
+    pub fn tap(&self) -> Fallible<&Self> {
+        let midpoint = self.wait_for_actionable()?;
+        debug!("Tapping element {:?}", &self);
+        self.parent.touchscreen().tap(midpoint)?;
+        Ok(self)
+    }
+
+    /// Right-clicks the middle of this element, once it's
+    /// [actionable](#method.wait_for_actionable).
+    pub fn right_click(&self) -> Fallible<&Self> {
+        let midpoint = self.wait_for_actionable()?;
+        debug!("Right-clicking element {:?}", &self);
+        self.parent.mouse().right_click(midpoint)?;
+        Ok(self)
+    }
+
+    /// Scrolls this element into view and waits until it's attached to the document, visible,
+    /// enabled, no longer moving, and what's hit when clicking its middle, which is returned.
+    ///
+    /// If that doesn't happen within the tab's default timeout, this fails with the
+    /// [`NotActionable`](enum.NotActionable.html) reason it was last waiting on, e.g. "element is
+    /// covered by <div class=modal>". It fails straight away if the element is detached.
+    pub fn wait_for_actionable(&self) -> Fallible<Point> {
+        let timeout = *self.parent.default_timeout.read().unwrap();
+        let mut reason = None;
+        let result = util::Wait::with_timeout(timeout).until(|| match self.check_actionable() {
+            Ok(midpoint) => Some(Ok(midpoint)),
+            Err(error) => match error.downcast::<NotActionable>() {
+                Ok(NotActionable::Detached) => Some(Err(NotActionable::Detached.into())),
+                Ok(not_actionable) => {
+                    trace!("Waiting for {:?}: {}", self, not_actionable);
+                    reason = Some(not_actionable);
+                    None
+                }
+                Err(error) => Some(Err(error)),
+            },
+        });
+        match result {
+            Ok(result) => result,
+            Err(timeout) => Err(reason.map_or_else(|| timeout.into(), Into::into)),
+        }
+    }
+
+    fn check_actionable(&self) -> Fallible<Point> {
+        self.scroll_into_view()
+            .map_err(|error| match error.downcast_ref::<ScrollFailed>() {
+                Some(failure) if failure.error_text == "Node is detached from document" => {
+                    NotActionable::Detached.into()
+                }
+                _ => error,
+            })?;
+
+        let state = self.call_js_fn(
+            "async function() {
+                if (!this.isConnected)
+                    return 'detached';
+                const rect = this.getBoundingClientRect();
+                if (rect.width === 0 || rect.height === 0
+                    || getComputedStyle(this).visibility !== 'visible')
+                    return 'hidden';
+                if (this.matches(':disabled') || this.getAttribute('aria-disabled') === 'true')
+                    return 'disabled';
+                // animation frames don't fire in background tabs, hence the timeout
+                await new Promise(resolve => {
+                    requestAnimationFrame(() => requestAnimationFrame(resolve));
+                    setTimeout(resolve, 100);
+                });
+                const after = this.getBoundingClientRect();
+                if (rect.x !== after.x || rect.y !== after.y
+                    || rect.width !== after.width || rect.height !== after.height)
+                    return 'moving';
+                return null;
+            }",
+            true,
+        )?;
+        match state.value.as_ref().and_then(serde_json::Value::as_str) {
+            Some("detached") => return Err(NotActionable::Detached.into()),
+            Some("hidden") => return Err(NotActionable::NotVisible.into()),
+            Some("disabled") => return Err(NotActionable::Disabled.into()),
+            Some("moving") => return Err(NotActionable::NotStable.into()),
+            _ => {}
+        }
+
+        let midpoint = self.get_midpoint()?;
+        if let Some(covered_by) = self.get_covering_element(midpoint)? {
+            return Err(NotActionable::Covered(covered_by).into());
+        }
+        Ok(midpoint)
+    }
+
+    // describes the element hit at `point`, unless it's this element or one inside it
+    fn get_covering_element(&self, point: Point) -> Fallible<Option<String>> {
+        #[allow(clippy::cast_possible_truncation)]
+        let hit = self.parent.call_method(dom::methods::GetNodeForLocation {
+            x: point.x.floor() as JsInt,
+            y: point.y.floor() as JsInt,
+            ..Default::default()
+        })?;
+        if hit.backend_node_id == self.backend_node_id {
+            return Ok(None);
+        }
+        let hit_object_id = self
+            .parent
+            .call_method(dom::methods::ResolveNode {
+                backend_node_id: Some(hit.backend_node_id),
+            })?
+            .object
+            .object_id
+            .ok_or_else(|| format_err!("Couldn't resolve node {}", hit.backend_node_id))?;
+
+        let result = self.parent.call_method(runtime::methods::CallFunctionOn {
+            object_id: &self.remote_object_id,
+            function_declaration: "function(hit) {
+                for (let node = hit; node; node = node.parentNode || node.host) {
+                    if (node === this)
+                        return null;
+                }
+                const element = hit.nodeType === Node.ELEMENT_NODE ? hit : hit.parentElement;
+                if (!element)
+                    return hit.nodeName;
+                let description = '<' + element.localName;
+                if (element.id)
+                    description += ' id=' + element.id;
+                const className = element.getAttribute('class');
+                if (className)
+                    description += ' class=' + className;
+                return description + '>';
+            }",
+            arguments: Some(vec![runtime::methods::CallArgument {
+                object_id: Some(&hit_object_id),
+                ..Default::default()
+            }]),
+            return_by_value: true,
+            generate_preview: false,
+            silent: true,
+            await_promise: false,
+        });
+        match result {
+            Ok(return_object) => Ok(return_object
+                .result
+                .value
+                .as_ref()
+                .and_then(serde_json::Value::as_str)
+                .map(ToString::to_string)),
+            // the hit node is in another frame, so the two can't be compared in JS
+            Err(error) if is_in_other_world(&error) => {
+                let hit_node = self
+                    .parent
+                    .call_method(dom::methods::DescribeNode {
+                        node_id: None,
+                        backend_node_id: Some(hit.backend_node_id),
+                        depth: Some(0),
+                        pierce: None,
+                    })?
+                    .node;
+                Ok(Some(format!("<{}>", hit_node.local_name)))
+            }
+            Err(error) => Err(error),
+        }
+    }
+
     pub fn type_into(&self, text: &str) -> Fallible<&Self> {
         self.click()?;
 
@@ -102,6 +331,7 @@ impl<'a> Element<'a> {
             .call_method(runtime::methods::CallFunctionOn {
                 object_id: &self.remote_object_id,
                 function_declaration,
+                arguments: None,
                 return_by_value: false,
                 generate_preview: true,
                 silent: false,
@@ -161,6 +391,7 @@ impl<'a> Element<'a> {
                 node_id: None,
                 backend_node_id: Some(self.backend_node_id),
                 depth: Some(100),
+                pierce: None,
             })?
             .node;
         Ok(node)
@@ -263,13 +494,29 @@ impl<'a> Element<'a> {
         })
     }
 
+    /// Returns the middle of this element's first content quad, in CSS pixels relative to the
+    /// viewport. Fails with `NotActionable::NotVisible` if the element isn't rendered.
     pub fn get_midpoint(&self) -> Fallible<Point> {
-        let return_object = self.parent.call_method(dom::methods::GetContentQuads {
-            node_id: None,
-            backend_node_id: Some(self.backend_node_id),
-            object_id: None,
-        })?;
-        let raw_quad = return_object.quads.first().unwrap();
+        let return_object = self
+            .parent
+            .call_method(dom::methods::GetContentQuads {
+                node_id: None,
+                backend_node_id: Some(self.backend_node_id),
+                object_id: None,
+            })
+            .map_err(|error| match error.downcast::<RemoteError>() {
+                Ok(ref remote_error)
+                    if remote_error.message == "Could not compute content quads." =>
+                {
+                    NotActionable::NotVisible.into()
+                }
+                Ok(remote_error) => remote_error.into(),
+                Err(error) => error,
+            })?;
+        let raw_quad = return_object
+            .quads
+            .first()
+            .ok_or(NotActionable::NotVisible)?;
         let input_quad = ElementQuad::from_raw_points(&raw_quad);
 
         Ok((input_quad.bottom_right + input_quad.top_left) / 2.0)
@@ -299,8 +546,36 @@ impl<'a> Element<'a> {
     }
 }
 
+/// Why an element can't be clicked or hovered (yet).
+#[derive(Debug, Fail, Clone, PartialEq)]
+pub enum NotActionable {
+    #[fail(display = "element is not attached to the document")]
+    Detached,
+    #[fail(display = "element is not visible")]
+    NotVisible,
+    #[fail(display = "element is disabled")]
+    Disabled,
+    #[fail(display = "element is not stable, it's still moving")]
+    NotStable,
+    /// Another element, described like `<div class=modal>`, would receive the click.
+    #[fail(display = "element is covered by {}", _0)]
+    Covered(String),
+}
+
 #[derive(Debug, Fail)]
 #[fail(display = "Scrolling element into view failed: {}", error_text)]
-struct ScrollFailed {
+pub(crate) struct ScrollFailed {
     error_text: String,
 }
+
+// whether Chrome refused to pass an object to a function because they live in different frames
+// (or worlds) of the page
+fn is_in_other_world(error: &Error) -> bool {
+    error
+        .downcast_ref::<RemoteError>()
+        .map_or(false, |remote_error| {
+            remote_error
+                .message
+                .contains("should belong to the same JavaScript world")
+        })
+}
@@ -0,0 +1,178 @@
+use failure::{format_err, Fail, Fallible};
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+
+use super::Element;
+use crate::protocol::runtime;
+use crate::protocol::RemoteError;
+
+#[derive(Debug, Fail)]
+#[fail(display = "Element is detached from the document")]
+pub struct ElementDetached {}
+
+impl<'a> Element<'a> {
+    /// Whether the element takes up space on the page and isn't hidden with `visibility`.
+    pub fn is_visible(&self) -> Fallible<bool> {
+        self.query(
+            "function() {
+                const rect = this.getBoundingClientRect();
+                return rect.width > 0 && rect.height > 0
+                    && getComputedStyle(this).visibility === 'visible';
+            }",
+            &[],
+        )
+    }
+
+    /// Whether the element is neither disabled (itself or by a `<fieldset>`) nor marked with
+    /// `aria-disabled="true"`.
+    pub fn is_enabled(&self) -> Fallible<bool> {
+        self.query(
+            "function() {
+                return !this.matches(':disabled') && this.getAttribute('aria-disabled') !== 'true';
+            }",
+            &[],
+        )
+    }
+
+    /// Whether a checkbox or radio button (native, or one with an ARIA role) is checked. Fails
+    /// for any other element.
+    pub fn is_checked(&self) -> Fallible<bool> {
+        self.query(
+            "function() {
+                if (this.matches('input[type=checkbox], input[type=radio]'))
+                    return this.checked;
+                if (this.matches('[role=checkbox], [role=radio], [role=switch]'))
+                    return this.getAttribute('aria-checked') === 'true';
+                throw new Error('Not a checkbox or radio button');
+            }",
+            &[],
+        )
+    }
+
+    /// Whether the element is enabled and can be typed into: a form control which isn't
+    /// read-only, or content-editable.
+    pub fn is_editable(&self) -> Fallible<bool> {
+        self.query(
+            "function() {
+                if (this.matches(':disabled') || this.getAttribute('aria-disabled') === 'true')
+                    return false;
+                if (this.matches('input, textarea, select'))
+                    return !this.readOnly;
+                return this.isContentEditable;
+            }",
+            &[],
+        )
+    }
+
+    /// Returns the JavaScript property `name` of the element, e.g. `"checked"` or `"href"`,
+    /// deserialized from its JSON representation.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// let max_length: i64 = tab.find_element("input")?.get_property("maxLength")?;
+    /// let dataset: serde_json::Value = tab.find_element("div")?.get_property("dataset")?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn get_property<T: DeserializeOwned>(&self, name: &str) -> Fallible<T> {
+        self.query(
+            "function(name) { return this[name]; }",
+            &[Value::String(name.to_string())],
+        )
+    }
+
+    /// Returns the current value of an `<input>`, `<textarea>` or `<select>`, which unlike the
+    /// `value` attribute reflects what the user typed or picked.
+    pub fn get_value(&self) -> Fallible<String> {
+        self.query(
+            "function() {
+                if (!('value' in this))
+                    throw new Error('Element has no value');
+                return this.value;
+            }",
+            &[],
+        )
+    }
+
+    pub fn get_outer_html(&self) -> Fallible<String> {
+        self.query("function() { return this.outerHTML; }", &[])
+    }
+
+    /// Returns the text of the element and all its descendants, including hidden ones, as
+    /// opposed to `get_inner_text`, which only returns what's rendered.
+    pub fn get_text_content(&self) -> Fallible<String> {
+        self.query("function() { return this.textContent; }", &[])
+    }
+
+    /// Returns the computed value of the CSS property `property`, e.g. `"display"`.
+    pub fn get_computed_style(&self, property: &str) -> Fallible<String> {
+        self.query(
+            "function(property) { return getComputedStyle(this).getPropertyValue(property); }",
+            &[Value::String(property.to_string())],
+        )
+    }
+
+    // calls `function_declaration` on the element with `arguments`, and deserializes what it
+    // returns, failing with `ElementDetached` if the element isn't in the document anymore
+    pub(super) fn query<T: DeserializeOwned>(
+        &self,
+        function_declaration: &str,
+        arguments: &[Value],
+    ) -> Fallible<T> {
+        let function_declaration = format!(
+            "function(...args) {{
+                if (!this.isConnected)
+                    return {{ detached: true }};
+                return {{ value: ({}).apply(this, args) }};
+            }}",
+            function_declaration
+        );
+        let return_object = self
+            .parent
+            .call_method(runtime::methods::CallFunctionOn {
+                object_id: &self.remote_object_id,
+                function_declaration: &function_declaration,
+                arguments: Some(
+                    arguments
+                        .iter()
+                        .map(|argument| runtime::methods::CallArgument {
+                            value: Some(argument.clone()),
+                            ..Default::default()
+                        })
+                        .collect(),
+                ),
+                return_by_value: true,
+                generate_preview: false,
+                silent: true,
+                await_promise: false,
+            })
+            .map_err(|error| match error.downcast::<RemoteError>() {
+                // the object is gone along with the document it was in
+                Ok(ref remote_error)
+                    if remote_error.message == "Could not find object with given id" =>
+                {
+                    ElementDetached {}.into()
+                }
+                Ok(remote_error) => remote_error.into(),
+                Err(error) => error,
+            })?;
+
+        if let Some(exception_details) = return_object.exception_details {
+            let description = exception_details
+                .exception
+                .and_then(|exception| exception.description)
+                .unwrap_or(exception_details.text);
+            return Err(format_err!("Querying {:?} failed: {}", self, description));
+        }
+        let mut outcome = return_object.result.value.unwrap_or_default();
+        if outcome["detached"] == Value::Bool(true) {
+            return Err(ElementDetached {}.into());
+        }
+        Ok(serde_json::from_value(outcome["value"].take())?)
+    }
+}
@@ -0,0 +1,162 @@
+use failure::Fallible;
+use log::*;
+
+use crate::browser::tab::Tab;
+use crate::protocol::emulation::{self, MediaFeature, VisionDeficiency};
+
+/// Timezone, locale, media and vision deficiency settings for pages, to apply to a tab with
+/// `Tab::set_emulation_options` or to all new tabs of a context with
+/// `Context::with_emulation_options`. Whatever isn't set is left as it is.
+///
+/// ```rust,no_run
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// # use headless_chrome::Browser;
+/// use headless_chrome::browser::tab::EmulationOptions;
+///
+/// # let browser = Browser::default()?;
+/// let options = EmulationOptions::new()
+///     .with_timezone("Europe/Berlin")
+///     .with_locale("de-DE")
+///     .with_media_feature("prefers-color-scheme", "dark");
+/// let context = browser.new_context()?.with_emulation_options(options);
+/// let tab = context.new_tab()?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EmulationOptions {
+    timezone: Option<String>,
+    locale: Option<String>,
+    media_type: Option<String>,
+    media_features: Vec<(String, String)>,
+    vision_deficiency: Option<VisionDeficiency>,
+}
+
+impl EmulationOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// An IANA timezone like `"America/New_York"`.
+    pub fn with_timezone(mut self, timezone: &str) -> Self {
+        self.timezone = Some(timezone.to_string());
+        self
+    }
+
+    /// A locale like `"en-GB"`, which decides how `Intl` and `toLocaleString` format dates and
+    /// numbers.
+    pub fn with_locale(mut self, locale: &str) -> Self {
+        self.locale = Some(locale.to_string());
+        self
+    }
+
+    /// The CSS media type, `"screen"` or `"print"`.
+    pub fn with_media_type(mut self, media_type: &str) -> Self {
+        self.media_type = Some(media_type.to_string());
+        self
+    }
+
+    /// The value of a media feature, like `("prefers-color-scheme", "dark")`,
+    /// `("prefers-reduced-motion", "reduce")` or `("forced-colors", "active")`.
+    pub fn with_media_feature(mut self, name: &str, value: &str) -> Self {
+        self.media_features
+            .push((name.to_string(), value.to_string()));
+        self
+    }
+
+    pub fn with_vision_deficiency(mut self, vision_deficiency: VisionDeficiency) -> Self {
+        self.vision_deficiency = Some(vision_deficiency);
+        self
+    }
+}
+
+impl Tab {
+    /// Applies everything that's set in `options`.
+    pub fn set_emulation_options(&self, options: &EmulationOptions) -> Fallible<&Self> {
+        debug!("Applying {:?}", options);
+        if let Some(timezone) = &options.timezone {
+            self.set_timezone(Some(timezone))?;
+        }
+        if let Some(locale) = &options.locale {
+            self.set_locale(Some(locale))?;
+        }
+        if options.media_type.is_some() || !options.media_features.is_empty() {
+            let features: Vec<(&str, &str)> = options
+                .media_features
+                .iter()
+                .map(|(name, value)| (name.as_str(), value.as_str()))
+                .collect();
+            self.set_emulated_media(options.media_type.as_ref().map(String::as_str), &features)?;
+        }
+        if let Some(vision_deficiency) = options.vision_deficiency {
+            self.set_vision_deficiency(vision_deficiency)?;
+        }
+        Ok(self)
+    }
+
+    /// Makes pages believe they're in `timezone`, e.g. `"Asia/Tokyo"`, or back in the system's
+    /// timezone for `None`.
+    pub fn set_timezone(&self, timezone: Option<&str>) -> Fallible<&Self> {
+        self.call_method(emulation::methods::SetTimezoneOverride {
+            timezone_id: timezone.unwrap_or(""),
+        })?;
+        Ok(self)
+    }
+
+    /// Makes pages format dates and numbers for `locale`, e.g. `"fr-CA"`, or the system's
+    /// locale for `None`.
+    pub fn set_locale(&self, locale: Option<&str>) -> Fallible<&Self> {
+        // Chrome refuses to override a locale which is already overridden
+        self.call_method(emulation::methods::SetLocaleOverride { locale: None })?;
+        if locale.is_some() {
+            self.call_method(emulation::methods::SetLocaleOverride { locale })?;
+        }
+        Ok(self)
+    }
+
+    /// Makes CSS media queries match `media_type` (`"screen"` or `"print"`) and the given media
+    /// `features`, like `("prefers-color-scheme", "dark")`. Features that aren't given go back
+    /// to their actual values, so `set_emulated_media(None, &[])` turns the emulation off.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.set_emulated_media(
+    ///     None,
+    ///     &[("prefers-color-scheme", "dark"), ("prefers-reduced-motion", "reduce")],
+    /// )?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_emulated_media(
+        &self,
+        media_type: Option<&str>,
+        features: &[(&str, &str)],
+    ) -> Fallible<&Self> {
+        self.call_method(emulation::methods::SetEmulatedMedia {
+            media: Some(media_type.unwrap_or("")),
+            features: Some(
+                features
+                    .iter()
+                    .map(|&(name, value)| MediaFeature { name, value })
+                    .collect(),
+            ),
+        })?;
+        Ok(self)
+    }
+
+    /// Renders pages the way people with `vision_deficiency` see them, which shows up in
+    /// screenshots. `VisionDeficiency::None` turns this off again.
+    pub fn set_vision_deficiency(&self, vision_deficiency: VisionDeficiency) -> Fallible<&Self> {
+        self.call_method(emulation::methods::SetEmulatedVisionDeficiency {
+            deficiency_type: vision_deficiency,
+        })?;
+        Ok(self)
+    }
+}
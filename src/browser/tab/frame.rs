@@ -0,0 +1,129 @@
+use std::fmt::Debug;
+
+use failure::{format_err, Error, Fallible};
+use log::*;
+
+use crate::browser::tab::element::Element;
+use crate::browser::tab::selector::Selector;
+use crate::browser::tab::{NoElementFound, Tab};
+use crate::protocol::{dom, page, runtime};
+use crate::util;
+
+/// A handle to a frame of a page: either the main frame, or one created by an `<iframe>`.
+///
+/// Get these via `Tab::frames` or `Tab::main_frame`. Elements found through a frame live in that
+/// frame's document, and JavaScript evaluated through it runs in that frame's global scope.
+///
+/// Only frames running in the same process as the page are reachable; cross-site iframes which
+/// Chrome isolates into their own process are separate targets.
+pub struct Frame<'a> {
+    /// What the frame tree said about this frame when the handle was created.
+    pub info: page::Frame,
+    pub tab: &'a Tab,
+}
+
+impl<'a> Debug for Frame<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "Frame {}", self.info.id)?;
+        Ok(())
+    }
+}
+
+impl<'a> Frame<'a> {
+    pub fn new(info: page::Frame, tab: &'a Tab) -> Self {
+        Self { info, tab }
+    }
+
+    pub fn is_main_frame(&self) -> bool {
+        self.info.parent_id.is_none()
+    }
+
+    /// Returns the frames directly embedded in this one.
+    pub fn child_frames(&self) -> Fallible<Vec<Frame<'a>>> {
+        Ok(self
+            .tab
+            .frames()?
+            .into_iter()
+            .filter(|frame| frame.info.parent_id.as_ref() == Some(&self.info.id))
+            .collect())
+    }
+
+    /// Evaluates `expression` in the global scope of this frame, like `Tab::evaluate` does for
+    /// the main frame.
+    pub fn evaluate(
+        &self,
+        expression: &str,
+        await_promise: bool,
+    ) -> Fallible<runtime::methods::RemoteObject> {
+        let context_id = self.tab.execution_context_id(&self.info.id)?;
+        let result = self
+            .tab
+            .call_method(runtime::methods::Evaluate {
+                expression,
+                return_by_value: false,
+                generate_preview: true,
+                silent: false,
+                await_promise,
+                include_command_line_api: false,
+                user_gesture: false,
+                context_id: Some(context_id),
+            })?
+            .result;
+        Ok(result)
+    }
+
+    /// Returns the first element in this frame's document which matches the given selector.
+    pub fn find_element<S: Into<Selector>>(&self, selector: S) -> Fallible<Element<'a>> {
+        let selector = selector.into();
+        trace!(
+            "Looking up element in {:?} via selector: {}",
+            self,
+            selector
+        );
+
+        let document_node_id = self.document_node_id()?;
+        self.tab
+            .run_query_selector_on_node(document_node_id, selector)
+    }
+
+    pub fn find_elements<S: Into<Selector>>(&self, selector: S) -> Fallible<Vec<Element<'a>>> {
+        let selector = selector.into();
+        trace!(
+            "Looking up elements in {:?} via selector: {}",
+            self,
+            selector
+        );
+
+        let document_node_id = self.document_node_id()?;
+        self.tab
+            .run_query_selector_all_on_node(document_node_id, selector)
+    }
+
+    pub fn wait_for_element<S: Into<Selector>>(&self, selector: S) -> Fallible<Element<'a>> {
+        let selector = selector.into();
+        debug!(
+            "Waiting for element in {:?} with selector: {}",
+            self, selector
+        );
+        util::Wait::with_timeout(*self.tab.default_timeout.read().unwrap()).strict_until(
+            || self.find_element(&selector),
+            Error::downcast::<NoElementFound>,
+        )
+    }
+
+    fn document_node_id(&self) -> Fallible<dom::NodeId> {
+        // DOM.requestNode only works once the document has been sent to us
+        self.tab.get_document()?;
+
+        let document = self.evaluate("document", false)?;
+        let object_id = document
+            .object_id
+            .ok_or_else(|| format_err!("{:?} has no document", self))?;
+        Ok(self
+            .tab
+            .call_method(dom::methods::RequestNode {
+                object_id: &object_id,
+            })?
+            .node_id)
+    }
+}
@@ -0,0 +1,397 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, Mutex, Weak};
+use std::time::Duration;
+
+use failure::{format_err, Fail, Fallible};
+use log::*;
+use serde::de::DeserializeOwned;
+
+use crate::browser::tab::{SyncSendEvent, Tab};
+use crate::protocol::network;
+use crate::protocol::network::events::{
+    RequestWillBeSentEventParams, ResourceType, ResponseReceivedEventParams,
+};
+use crate::protocol::Event;
+use crate::util;
+
+/// Decides which request or response `Tab::wait_for_request` and `Tab::wait_for_response` are
+/// waiting for.
+///
+/// Implemented for URL globs, where `*` matches any number of characters and `?` exactly one
+/// (e.g. `"*/api/items"`), and for closures taking the request or response.
+pub trait NetworkMatcher<T> {
+    fn matches(&self, subject: &T) -> bool;
+}
+
+impl NetworkMatcher<network::Request> for &str {
+    fn matches(&self, request: &network::Request) -> bool {
+        glob_matches(self, &request.url)
+    }
+}
+
+impl NetworkMatcher<network::Response> for &str {
+    fn matches(&self, response: &network::Response) -> bool {
+        glob_matches(self, &response.url)
+    }
+}
+
+impl<T, F> NetworkMatcher<T> for F
+where
+    F: Fn(&T) -> bool,
+{
+    fn matches(&self, subject: &T) -> bool {
+        self(subject)
+    }
+}
+
+#[derive(Debug, Fail)]
+#[fail(display = "Loading the response failed: {}", error_text)]
+pub struct LoadingFailed {
+    pub error_text: String,
+}
+
+/// A request sent by the page, as returned by `Tab::wait_for_request`.
+pub struct HttpRequest<'a> {
+    pub request_id: String,
+    pub request: network::Request,
+    pub resource_type: Option<ResourceType>,
+    pub frame_id: Option<String>,
+    tab: &'a Tab,
+}
+
+impl<'a> Debug for HttpRequest<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{} {}", self.request.method, self.request.url)?;
+        Ok(())
+    }
+}
+
+impl<'a> HttpRequest<'a> {
+    fn new(params: RequestWillBeSentEventParams, tab: &'a Tab) -> Self {
+        Self {
+            request_id: params.request_id,
+            request: params.request,
+            resource_type: params._type,
+            frame_id: params.frame_id,
+            tab,
+        }
+    }
+
+    pub fn url(&self) -> &str {
+        &self.request.url
+    }
+
+    pub fn method(&self) -> &str {
+        &self.request.method
+    }
+
+    /// Returns the body of the request, if it has one.
+    ///
+    /// Chrome leaves large bodies out of the event announcing the request, in which case they're
+    /// fetched separately.
+    pub fn post_data(&self) -> Fallible<Option<String>> {
+        if let Some(post_data) = &self.request.post_data {
+            return Ok(Some(post_data.clone()));
+        }
+        if self.request.has_post_data != Some(true) {
+            return Ok(None);
+        }
+        let return_object = self.tab.call_method(network::methods::GetRequestPostData {
+            request_id: &self.request_id,
+        })?;
+        Ok(Some(return_object.post_data))
+    }
+
+    /// Parses the body of the request as JSON.
+    pub fn post_data_json<T: DeserializeOwned>(&self) -> Fallible<T> {
+        let post_data = self
+            .post_data()?
+            .ok_or_else(|| format_err!("{:?} has no body", self))?;
+        Ok(serde_json::from_str(&post_data)?)
+    }
+}
+
+#[derive(Default)]
+struct ResponseWatch {
+    // responses received while waiting for one which matches
+    candidates: Vec<ResponseReceivedEventParams>,
+    // set once a response matched, after which only its request is followed
+    request_id: Option<String>,
+    // whether each request finished loading, or the error text it failed with
+    outcomes: HashMap<String, Result<(), String>>,
+}
+
+/// A response received by the page, as returned by `Tab::wait_for_response`.
+///
+/// Its body only becomes available once Chrome has finished loading it, which `body` and friends
+/// wait for.
+pub struct HttpResponse<'a> {
+    pub request_id: String,
+    pub response: network::Response,
+    pub resource_type: ResourceType,
+    pub frame_id: Option<String>,
+    watch: Arc<Mutex<ResponseWatch>>,
+    listener: Weak<SyncSendEvent>,
+    // how long to wait for the body, the timeout `wait_for_response` was given
+    timeout: Duration,
+    tab: &'a Tab,
+}
+
+impl<'a> Debug for HttpResponse<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> Result<(), std::fmt::Error> {
+        write!(f, "{} {}", self.response.status, self.response.url)?;
+        Ok(())
+    }
+}
+
+impl<'a> Drop for HttpResponse<'a> {
+    fn drop(&mut self) {
+        if self.tab.remove_event_listener(&self.listener).is_err() {
+            warn!("Couldn't stop following {:?}", self);
+        }
+    }
+}
+
+impl<'a> HttpResponse<'a> {
+    pub fn url(&self) -> &str {
+        &self.response.url
+    }
+
+    pub fn status(&self) -> u32 {
+        self.response.status
+    }
+
+    /// Waits until the response has finished loading, failing with `LoadingFailed` if it doesn't.
+    /// It gets as long as `wait_for_response` got to wait for the response.
+    pub fn wait_for_finished(&self) -> Fallible<()> {
+        let outcome = util::Wait::with_timeout(self.timeout).until(|| {
+            let watch = self.watch.lock().unwrap();
+            watch.outcomes.get(&self.request_id).cloned()
+        })?;
+        outcome.map_err(|error_text| LoadingFailed { error_text }.into())
+    }
+
+    /// Returns the body of the response, waiting for it to finish loading first.
+    pub fn body(&self) -> Fallible<Vec<u8>> {
+        self.wait_for_finished()?;
+        let return_object = self.tab.call_method(network::methods::GetResponseBody {
+            request_id: &self.request_id,
+        })?;
+        if return_object.base64_encoded {
+            Ok(base64::decode(&return_object.body)?)
+        } else {
+            Ok(return_object.body.into_bytes())
+        }
+    }
+
+    pub fn text(&self) -> Fallible<String> {
+        Ok(String::from_utf8(self.body()?)?)
+    }
+
+    pub fn json<T: DeserializeOwned>(&self) -> Fallible<T> {
+        Ok(serde_json::from_slice(&self.body()?)?)
+    }
+}
+
+impl Tab {
+    /// Waits for the page to send a request `matcher` accepts, which is either a URL glob or a
+    /// closure taking the `network::Request`.
+    ///
+    /// Only requests sent after this is called are considered, so whatever sends the request must
+    /// not do so before the wait has begun, e.g. by scheduling it with `setTimeout`.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use std::time::Duration;
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::protocol::network::Request;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.evaluate("setTimeout(() => document.querySelector('#save').click(), 100)", false)?;
+    /// let request = tab.wait_for_request(
+    ///     |request: &Request| request.method == "POST" && request.url.ends_with("/api/items"),
+    ///     Duration::from_secs(5),
+    /// )?;
+    /// let item: serde_json::Value = request.post_data_json()?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_request<M>(&self, matcher: M, timeout: Duration) -> Fallible<HttpRequest<'_>>
+    where
+        M: NetworkMatcher<network::Request>,
+    {
+        self.call_method(network::methods::Enable {})?;
+
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let requests_clone = Arc::clone(&requests);
+        let listener = self.add_event_listener(Arc::new(move |event: &Event| {
+            if let Event::RequestWillBeSent(event) = event {
+                requests_clone.lock().unwrap().push(event.params.clone());
+            }
+        }))?;
+
+        let result = util::Wait::with_timeout(timeout).until(|| {
+            let mut requests = requests.lock().unwrap();
+            let position = requests
+                .iter()
+                .position(|params| matcher.matches(&params.request))?;
+            Some(requests.swap_remove(position))
+        });
+        self.remove_event_listener(&listener)?;
+
+        let params = result?;
+        debug!("Request {} matched", params.request_id);
+        Ok(HttpRequest::new(params, self))
+    }
+
+    /// Waits for the page to receive a response `matcher` accepts, which is either a URL glob or
+    /// a closure taking the `network::Response`.
+    ///
+    /// As with `wait_for_request`, only responses received after this is called are considered.
+    /// The response returned keeps following its request until dropped, so its body can be read
+    /// once it has finished loading.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use std::time::Duration;
+    /// # use headless_chrome::Browser;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.evaluate("setTimeout(() => fetch('/api/items'), 100)", false)?;
+    /// let response = tab.wait_for_response("*/api/items", Duration::from_secs(5))?;
+    /// assert_eq!(response.status(), 200);
+    /// let items: serde_json::Value = response.json()?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_response<M>(&self, matcher: M, timeout: Duration) -> Fallible<HttpResponse<'_>>
+    where
+        M: NetworkMatcher<network::Response>,
+    {
+        self.call_method(network::methods::Enable {})?;
+
+        let watch = Arc::new(Mutex::new(ResponseWatch::default()));
+        let watch_clone = Arc::clone(&watch);
+        let listener = self.add_event_listener(Arc::new(move |event: &Event| {
+            let mut watch = watch_clone.lock().unwrap();
+            let (request_id, outcome) = match event {
+                Event::ResponseReceived(event) => {
+                    if watch.request_id.is_none() {
+                        watch.candidates.push(event.params.clone());
+                    }
+                    return;
+                }
+                Event::LoadingFinished(event) => (&event.params.request_id, Ok(())),
+                Event::LoadingFailed(event) => (
+                    &event.params.request_id,
+                    Err(event.params.error_text.clone()),
+                ),
+                _ => return,
+            };
+            let followed = watch
+                .request_id
+                .as_ref()
+                .map_or(true, |id| id == request_id);
+            if followed {
+                watch.outcomes.insert(request_id.clone(), outcome);
+            }
+        }))?;
+
+        let result = util::Wait::with_timeout(timeout).until(|| {
+            let mut watch = watch.lock().unwrap();
+            let position = watch
+                .candidates
+                .iter()
+                .position(|params| matcher.matches(&params.response))?;
+            let params = watch.candidates.swap_remove(position);
+
+            watch.candidates.clear();
+            watch.outcomes.retain(|id, _| id == &params.request_id);
+            watch.request_id = Some(params.request_id.clone());
+            Some(params)
+        });
+        let params = match result {
+            Ok(params) => params,
+            Err(error) => {
+                self.remove_event_listener(&listener)?;
+                return Err(error.into());
+            }
+        };
+        debug!("Response to {} matched", params.request_id);
+
+        Ok(HttpResponse {
+            request_id: params.request_id,
+            response: params.response,
+            resource_type: params._type,
+            frame_id: params.frame_id,
+            watch,
+            listener,
+            timeout,
+            tab: self,
+        })
+    }
+}
+
+// matches `text` against `pattern`, where `*` stands for any number of characters and `?` for
+// exactly one
+fn glob_matches(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+
+    let (mut p, mut t) = (0, 0);
+    // where the last `*` was in the pattern, and where in the text it started matching
+    let mut backtrack = None;
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // let the last `*` swallow one more character
+                Some((star, start)) => {
+                    backtrack = Some((star, start + 1));
+                    p = star + 1;
+                    t = start + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::glob_matches;
+
+    #[test]
+    fn matches_globs() {
+        assert!(glob_matches("*", "http://127.0.0.1/"));
+        assert!(glob_matches(
+            "*/api/items",
+            "http://127.0.0.1:8000/api/items"
+        ));
+        assert!(glob_matches(
+            "*/api/*",
+            "http://127.0.0.1:8000/api/items?page=2"
+        ));
+        assert!(glob_matches("http://?ocalhost/*", "http://localhost/"));
+        assert!(glob_matches("*a*b*", "xxaxxbxx"));
+        assert!(!glob_matches(
+            "*/api/items",
+            "http://127.0.0.1:8000/api/items/1"
+        ));
+        assert!(!glob_matches("http://?ocalhost/", "http://ocalhost/"));
+        assert!(!glob_matches("*.css", "http://127.0.0.1/style.css.map"));
+    }
+}
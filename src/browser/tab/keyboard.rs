@@ -0,0 +1,216 @@
+use failure::{format_err, Fallible};
+use log::*;
+
+use crate::browser::tab::keys::{self, KeyDefinition};
+use crate::browser::tab::mouse::Modifiers;
+use crate::browser::tab::Tab;
+use crate::protocol::input;
+
+/// The keyboard of a tab, which remembers which modifier keys are held down.
+///
+/// Keys are named as in the [US keyboard layout](https://developer.mozilla.org/en-US/docs/Web/API/KeyboardEvent/key/Key_Values),
+/// by what they type (`"a"`, `"A"`, `"%"`) or what they do (`"Enter"`, `"ArrowLeft"`,
+/// `"Shift"`), or by where they are (`"KeyA"`, `"ShiftRight"`).
+///
+/// ```rust,no_run
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// # use headless_chrome::Browser;
+/// # let browser = Browser::default()?;
+/// let tab = browser.wait_for_initial_tab()?;
+/// let keyboard = tab.keyboard();
+/// tab.find_element("textarea")?.click()?;
+/// keyboard.type_str("Hello 世界 👋")?;
+/// keyboard.press("Shift+Home")?.press("Control+X")?;
+/// keyboard.down("Shift")?;
+/// tab.find_element("li:last-child")?.click()?; // a shift-click
+/// keyboard.up("Shift")?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub struct Keyboard<'a> {
+    tab: &'a Tab,
+}
+
+impl<'a> Keyboard<'a> {
+    pub fn new(tab: &'a Tab) -> Self {
+        Self { tab }
+    }
+
+    /// Presses `key` and keeps it held down. If it's a modifier key, the keyboard and mouse
+    /// events that follow report it as held down until it's released with `up`.
+    pub fn down(&self, key: &str) -> Fallible<&Self> {
+        let definition = keys::get_key_definition(key)?;
+        let modifiers = {
+            let mut modifiers = self.tab.modifiers.lock().unwrap();
+            *modifiers = *modifiers | modifier_of(definition);
+            *modifiers
+        };
+
+        // See https://github.com/GoogleChrome/puppeteer/blob/62da2366c65b335751896afbb0206f23c61436f1/lib/Input.js#L114-L115
+        // Holding anything but Shift turns a key into a shortcut, which doesn't type anything.
+        let text = if modifiers.without(Modifiers::SHIFT) == Modifiers::NONE {
+            definition.text.or_else(|| {
+                if definition.key.len() == 1 {
+                    Some(definition.key)
+                } else {
+                    None
+                }
+            })
+        } else {
+            None
+        };
+
+        // See https://github.com/GoogleChrome/puppeteer/blob/62da2366c65b335751896afbb0206f23c61436f1/lib/Input.js#L52
+        let event_type = if text.is_some() {
+            "keyDown"
+        } else {
+            "rawKeyDown"
+        };
+
+        self.tab.call_method(input::methods::DispatchKeyEvent {
+            event_type,
+            key: Some(definition.key),
+            text,
+            code: Some(definition.code),
+            windows_virtual_key_code: definition.key_code,
+            native_virtual_key_code: definition.key_code,
+            modifiers: Some(modifiers.bits()),
+        })?;
+        Ok(self)
+    }
+
+    /// Releases `key`.
+    pub fn up(&self, key: &str) -> Fallible<&Self> {
+        let definition = keys::get_key_definition(key)?;
+        let modifiers = {
+            let mut modifiers = self.tab.modifiers.lock().unwrap();
+            *modifiers = modifiers.without(modifier_of(definition));
+            *modifiers
+        };
+
+        self.tab.call_method(input::methods::DispatchKeyEvent {
+            event_type: "keyUp",
+            key: Some(definition.key),
+            text: None,
+            code: Some(definition.code),
+            windows_virtual_key_code: definition.key_code,
+            native_virtual_key_code: definition.key_code,
+            modifiers: Some(modifiers.bits()),
+        })?;
+        Ok(self)
+    }
+
+    /// Presses and releases a key, or a chord of keys joined by `+` such as `"Control+Shift+K"`
+    /// or `"Shift++"`, in which all but the last are held down while the last one is pressed.
+    pub fn press(&self, chord: &str) -> Fallible<&Self> {
+        let keys = parse_chord(chord)?;
+        // a chord with a key that doesn't exist must fail before anything is held down
+        for key in &keys {
+            keys::get_key_definition(key)?;
+        }
+        trace!("Pressing {:?}", keys);
+
+        self.tab.optional_slow_motion_sleep(25);
+
+        // keys are released in reverse, including when pressing one of them fails, so no
+        // modifier stays held down for the keys and clicks that follow
+        let mut pressed = Vec::with_capacity(keys.len());
+        let mut result = Ok(());
+        for key in &keys {
+            result = self.down(key).map(|_| ());
+            // a failed press may still count as held down
+            pressed.push(*key);
+            if result.is_err() {
+                break;
+            }
+        }
+        for key in pressed.iter().rev() {
+            let released = self.up(key).map(|_| ());
+            result = result.and(released);
+        }
+        result?;
+        Ok(self)
+    }
+
+    /// Types `text` a character at a time. Characters which have a key are pressed, the rest
+    /// (like emoji or Chinese characters) are inserted with `insert_text`.
+    pub fn type_str(&self, text: &str) -> Fallible<&Self> {
+        for character in text.chars() {
+            match character {
+                '\n' | '\r' => self.press("Enter")?,
+                _ => {
+                    let character = character.to_string();
+                    if keys::get_key_definition(&character).is_ok() {
+                        self.press(&character)?
+                    } else {
+                        self.insert_text(&character)?
+                    }
+                }
+            };
+        }
+        Ok(self)
+    }
+
+    /// Inserts `text` into the focused element the way an IME or emoji keyboard would, firing
+    /// `input` events but no key events.
+    pub fn insert_text(&self, text: &str) -> Fallible<&Self> {
+        trace!("Inserting text: {}", text);
+        self.tab.call_method(input::methods::InsertText { text })?;
+        Ok(self)
+    }
+}
+
+fn modifier_of(definition: &KeyDefinition) -> Modifiers {
+    match definition.key {
+        "Alt" => Modifiers::ALT,
+        "Control" => Modifiers::CTRL,
+        "Meta" => Modifiers::META,
+        "Shift" => Modifiers::SHIFT,
+        _ => Modifiers::NONE,
+    }
+}
+
+// splits a chord like "Control+Shift+K" into its keys, allowing "+" itself as the last one
+fn parse_chord(chord: &str) -> Fallible<Vec<&str>> {
+    let keys: Vec<&str> = if chord == "+" {
+        vec!["+"]
+    } else if chord.ends_with("++") {
+        let mut keys: Vec<&str> = chord[..chord.len() - 2].split('+').collect();
+        keys.push("+");
+        keys
+    } else {
+        chord.split('+').collect()
+    };
+    if keys.iter().any(|key| key.is_empty()) {
+        return Err(format_err!("Invalid key chord: {:?}", chord));
+    }
+    Ok(keys)
+}
+
+impl Tab {
+    /// Returns the tab's [`Keyboard`](keyboard/struct.Keyboard.html).
+    pub fn keyboard(&self) -> Keyboard<'_> {
+        Keyboard::new(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::parse_chord;
+
+    #[test]
+    fn parses_chords() {
+        assert_eq!(parse_chord("a").unwrap(), vec!["a"]);
+        assert_eq!(parse_chord("+").unwrap(), vec!["+"]);
+        assert_eq!(
+            parse_chord("Control+Shift+K").unwrap(),
+            vec!["Control", "Shift", "K"]
+        );
+        assert_eq!(parse_chord("Shift++").unwrap(), vec!["Shift", "+"]);
+        assert!(parse_chord("").is_err());
+        assert!(parse_chord("Control+").is_err());
+        assert!(parse_chord("Control++Shift").is_err());
+    }
+}
@@ -0,0 +1,341 @@
+use std::fmt;
+
+use failure::{Error, Fallible};
+use log::*;
+use serde::de::DeserializeOwned;
+
+use crate::browser::tab::element::{Element, ElementDetached, NotActionable, ScrollFailed};
+use crate::browser::tab::selector::Selector;
+use crate::browser::tab::{NoElementFound, Tab};
+use crate::protocol::dom::{self, NodeId};
+use crate::protocol::{runtime, RemoteError};
+use crate::util;
+
+// what Chrome says when a node or object ID refers to something which has since been replaced,
+// e.g. because the page re-rendered or navigated
+const STALE_ERROR_MESSAGES: &[&str] = &[
+    "Could not find node with given id",
+    "No node with given id found",
+    "Node with given id does not belong to the document",
+    "Could not find object with given id",
+    "Cannot find context with specified id",
+];
+
+/// A way of finding an element, which is only followed when it's acted upon.
+///
+/// Unlike an [`Element`](../element/struct.Element.html), which refers to one DOM node and breaks
+/// once the page replaces that node (as frameworks like React do all the time), a locator looks
+/// the element up again for every action. Until the tab's default timeout is up, actions are
+/// retried for as long as the element is missing or gets replaced while they run.
+///
+/// ```rust,no_run
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// # use headless_chrome::Browser;
+/// # let browser = Browser::default()?;
+/// let tab = browser.wait_for_initial_tab()?;
+/// let todos = tab.locator("#todos");
+/// todos.locator("input.new").type_into("Buy milk")?;
+/// todos.locator("text=Add").click()?;
+/// assert_eq!(todos.locator("li").nth(0).get_inner_text()?, "Buy milk");
+/// #
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Locator<'a> {
+    tab: &'a Tab,
+    steps: Vec<Step>,
+}
+
+// one selector of the chain, and which of its matches to carry on from
+#[derive(Clone, Debug)]
+struct Step {
+    selector: Selector,
+    index: usize,
+}
+
+impl<'a> fmt::Debug for Locator<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Locator ")?;
+        for (i, step) in self.steps.iter().enumerate() {
+            if i > 0 {
+                write!(f, " >> ")?;
+            }
+            write!(f, "{}", step.selector)?;
+            if step.index > 0 {
+                write!(f, " >> nth={}", step.index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<'a> Locator<'a> {
+    pub fn new<S: Into<Selector>>(tab: &'a Tab, selector: S) -> Self {
+        Self {
+            tab,
+            steps: vec![Step {
+                selector: selector.into(),
+                index: 0,
+            }],
+        }
+    }
+
+    /// Returns a locator for elements matching `selector` below the element this one finds.
+    pub fn locator<S: Into<Selector>>(&self, selector: S) -> Self {
+        let mut locator = self.clone();
+        locator.steps.push(Step {
+            selector: selector.into(),
+            index: 0,
+        });
+        locator
+    }
+
+    /// Returns a locator for the `index`th (counting from zero) of the elements this one
+    /// matches, rather than the first.
+    pub fn nth(&self, index: usize) -> Self {
+        let mut locator = self.clone();
+        locator.steps.last_mut().unwrap().index = index;
+        locator
+    }
+
+    /// Looks the element up once, without waiting for it.
+    pub fn resolve(&self) -> Fallible<Element<'a>> {
+        let (last, path) = self.steps.split_last().unwrap();
+        let node_id = self.resolve_path(path)?;
+        let node_id = self
+            .tab
+            .query_selector_all(node_id, &last.selector)?
+            .get(last.index)
+            .copied()
+            .ok_or(NoElementFound {})?;
+        Element::new(self.tab, node_id)
+    }
+
+    /// Looks up all elements the last selector of the chain matches, without waiting.
+    pub fn resolve_all(&self) -> Fallible<Vec<Element<'a>>> {
+        let (last, path) = self.steps.split_last().unwrap();
+        let node_id = self.resolve_path(path)?;
+        self.tab
+            .query_selector_all(node_id, &last.selector)?
+            .into_iter()
+            .map(|node_id| Element::new(self.tab, node_id))
+            .collect()
+    }
+
+    /// Returns how many elements the last selector of the chain currently matches.
+    pub fn count(&self) -> Fallible<usize> {
+        match self.resolve_all() {
+            Ok(elements) => Ok(elements.len()),
+            Err(error) => match error.downcast::<NoElementFound>() {
+                Ok(_) => Ok(0),
+                Err(error) => Err(error),
+            },
+        }
+    }
+
+    /// Waits for the element to exist, and returns it.
+    pub fn wait_for(&self) -> Fallible<Element<'a>> {
+        let timeout = *self.tab.default_timeout.read().unwrap();
+        util::Wait::with_timeout(timeout).strict_until(|| self.resolve(), ignore_stale)
+    }
+
+    pub fn click(&self) -> Fallible<&Self> {
+        self.act(|element| element.click().map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn double_click(&self) -> Fallible<&Self> {
+        self.act(|element| element.double_click().map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn tap(&self) -> Fallible<&Self> {
+        self.act(|element| element.tap().map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn right_click(&self) -> Fallible<&Self> {
+        self.act(|element| element.right_click().map(|_| ()))?;
+        Ok(self)
+    }
+
+    /// Drags the element onto the one `target` finds, see `Element::drag_to`.
+    pub fn drag_to(&self, target: &Locator) -> Fallible<&Self> {
+        self.act(|element| element.drag_to(&target.resolve()?).map(|_| ()))?;
+        Ok(self)
+    }
+
+    /// Clicks the element and types `text` into it. Only clicking is retried, so the text is
+    /// never typed twice.
+    pub fn type_into(&self, text: &str) -> Fallible<&Self> {
+        self.click()?;
+        debug!("Typing into {:?}: {}", self, text);
+        self.tab.type_str(text)?;
+        Ok(self)
+    }
+
+    pub fn fill(&self, text: &str) -> Fallible<&Self> {
+        self.act(|element| element.fill(text).map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn clear(&self) -> Fallible<&Self> {
+        self.act(|element| element.clear().map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn select_option(&self, values: &[&str]) -> Fallible<Vec<String>> {
+        self.act(|element| element.select_option(values))
+    }
+
+    pub fn check(&self) -> Fallible<&Self> {
+        self.set_checked(true)
+    }
+
+    pub fn uncheck(&self) -> Fallible<&Self> {
+        self.set_checked(false)
+    }
+
+    pub fn set_checked(&self, checked: bool) -> Fallible<&Self> {
+        self.act(|element| element.set_checked(checked).map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn focus(&self) -> Fallible<&Self> {
+        self.act(|element| element.focus().map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn move_mouse_over(&self) -> Fallible<&Self> {
+        self.act(|element| element.move_mouse_over().map(|_| ()))?;
+        Ok(self)
+    }
+
+    pub fn get_inner_text(&self) -> Fallible<String> {
+        self.act(Element::get_inner_text)
+    }
+
+    pub fn get_attributes(&self) -> Fallible<Option<dom::NodeAttributes>> {
+        self.act(Element::get_attributes)
+    }
+
+    pub fn get_text_content(&self) -> Fallible<String> {
+        self.act(Element::get_text_content)
+    }
+
+    pub fn get_outer_html(&self) -> Fallible<String> {
+        self.act(Element::get_outer_html)
+    }
+
+    pub fn get_value(&self) -> Fallible<String> {
+        self.act(Element::get_value)
+    }
+
+    pub fn get_property<T: DeserializeOwned>(&self, name: &str) -> Fallible<T> {
+        self.act(|element| element.get_property(name))
+    }
+
+    pub fn get_computed_style(&self, property: &str) -> Fallible<String> {
+        self.act(|element| element.get_computed_style(property))
+    }
+
+    /// Whether the element exists and is visible. Doesn't wait for it to appear.
+    pub fn is_visible(&self) -> Fallible<bool> {
+        match self.resolve().and_then(|element| element.is_visible()) {
+            Ok(visible) => Ok(visible),
+            Err(error) => match ignore_stale(error) {
+                Ok(_) => Ok(false),
+                Err(error) => Err(error),
+            },
+        }
+    }
+
+    pub fn is_enabled(&self) -> Fallible<bool> {
+        self.act(Element::is_enabled)
+    }
+
+    pub fn is_checked(&self) -> Fallible<bool> {
+        self.act(Element::is_checked)
+    }
+
+    pub fn is_editable(&self) -> Fallible<bool> {
+        self.act(Element::is_editable)
+    }
+
+    pub fn call_js_fn(
+        &self,
+        function_declaration: &str,
+        await_promise: bool,
+    ) -> Fallible<runtime::methods::RemoteObject> {
+        self.act(|element| element.call_js_fn(function_declaration, await_promise))
+    }
+
+    /// Looks the element up and runs `action` on it, starting over if either fails because the
+    /// element is missing or was replaced, until the tab's default timeout is up.
+    pub fn act<F, T>(&self, action: F) -> Fallible<T>
+    where
+        F: Fn(&Element<'a>) -> Fallible<T>,
+    {
+        let timeout = *self.tab.default_timeout.read().unwrap();
+        util::Wait::with_timeout(timeout).strict_until(
+            || {
+                let element = self.resolve()?;
+                trace!("{:?} resolved to {:?}", self, element);
+                action(&element)
+            },
+            ignore_stale,
+        )
+    }
+
+    // follows all but the last selector of the chain, returning the node to query the last one on
+    fn resolve_path(&self, path: &[Step]) -> Fallible<NodeId> {
+        let mut node_id = self.tab.get_document()?.node_id;
+        for step in path {
+            node_id = self
+                .tab
+                .query_selector_all(node_id, &step.selector)?
+                .get(step.index)
+                .copied()
+                .ok_or(NoElementFound {})?;
+        }
+        Ok(node_id)
+    }
+}
+
+// treats errors caused by the element being missing or replaced as worth retrying
+fn ignore_stale(error: Error) -> Fallible<NoElementFound> {
+    let error = match error.downcast::<NoElementFound>() {
+        Ok(no_element_found) => return Ok(no_element_found),
+        Err(error) => error,
+    };
+    let error = match error.downcast::<ScrollFailed>() {
+        Ok(_) => return Ok(NoElementFound {}),
+        Err(error) => error,
+    };
+    let error = match error.downcast::<ElementDetached>() {
+        Ok(_) => return Ok(NoElementFound {}),
+        Err(error) => error,
+    };
+    let error = match error.downcast::<NotActionable>() {
+        Ok(NotActionable::Detached) => return Ok(NoElementFound {}),
+        Ok(not_actionable) => return Err(not_actionable.into()),
+        Err(error) => error,
+    };
+    match error.downcast::<RemoteError>() {
+        Ok(remote_error) if STALE_ERROR_MESSAGES.contains(&remote_error.message.as_str()) => {
+            Ok(NoElementFound {})
+        }
+        Ok(remote_error) => Err(remote_error.into()),
+        Err(error) => Err(error),
+    }
+}
+
+impl Tab {
+    /// Returns a [`Locator`](locator/struct.Locator.html) for the element `selector` finds,
+    /// which looks it up afresh every time it's used.
+    pub fn locator<S: Into<Selector>>(&self, selector: S) -> Locator<'_> {
+        Locator::new(self, selector)
+    }
+}
@@ -3,20 +3,25 @@ use std::sync::{Arc, Mutex, RwLock, Weak};
 use std::thread;
 use std::time::Duration;
 
-use failure::{Error, Fail, Fallible};
+use failure::{format_err, Error, Fail, Fallible};
 use log::*;
 use serde;
 
 use element::Element;
-use point::Point;
+pub use emulation_options::EmulationOptions;
+use frame::Frame;
+use mouse::MouseButton;
+pub use point::Point;
+use selector::Selector;
 
 use crate::protocol::dom::{Node, NodeId};
 use crate::protocol::page::methods::{
     FileChooserAction, HandleFileChooser, Navigate, SetInterceptFileChooserDialog,
 };
 use crate::protocol::target::{TargetId, TargetInfo};
+use crate::protocol::types::JsInt;
 use crate::protocol::{
-    dom, fetch, input, logs, network, page, profiler, runtime, target, Event, RemoteError,
+    dom, emulation, fetch, logs, network, page, profiler, runtime, target, Event, RemoteError,
 };
 use crate::{protocol, protocol::logs::methods::ViolationSetting, util};
 
@@ -24,12 +29,25 @@ use super::transport::SessionId;
 use crate::browser::transport::Transport;
 use crate::protocol::fetch::events::RequestPausedEvent;
 use crate::protocol::fetch::methods::{AuthChallengeResponse, ContinueRequest};
+use crate::protocol::network::events::ResourceType;
 use crate::protocol::network::methods::SetExtraHTTPHeaders;
-use crate::protocol::network::{Cookie, CookieParam};
+use crate::protocol::network::{Cookie, CookieParam, NetworkConditions};
 use std::collections::HashMap;
+use std::convert::TryFrom;
 use std::thread::sleep;
 
+pub mod console;
+pub mod device;
+pub mod dialog;
 pub mod element;
+pub mod emulation_options;
+pub mod frame;
+pub mod http;
+pub mod keyboard;
+pub mod locator;
+pub mod mouse;
+pub mod selector;
+pub mod touchscreen;
 mod keys;
 mod point;
 
@@ -94,13 +112,23 @@ pub struct Tab {
     transport: Arc<Transport>,
     session_id: SessionId,
     navigating: Arc<AtomicBool>,
+    // whether `watch_navigation` already enabled the Network domain, which is never disabled
+    network_enabled: Arc<AtomicBool>,
     target_info: Arc<Mutex<TargetInfo>>,
     request_interceptor: Arc<Mutex<Arc<RequestIntercept>>>,
     response_handler: Arc<Mutex<Option<ResponseHandler>>>,
     auth_handler: Arc<Mutex<fetch::methods::AuthChallengeResponse>>,
     default_timeout: Arc<RwLock<Duration>>,
     event_listeners: Arc<Mutex<Vec<Arc<SyncSendEvent>>>>,
+    // the id of the main world execution context of each frame, by frame id
+    execution_contexts: Arc<Mutex<HashMap<String, JsInt>>>,
     slow_motion_multiplier: Arc<RwLock<f64>>, // there's no AtomicF64, otherwise would use that
+    mouse_state: Arc<Mutex<mouse::MouseState>>,
+    // the modifier keys held down, shared by the keyboard and the mouse
+    modifiers: Arc<Mutex<mouse::Modifiers>>,
+    dialog_handler: Arc<Mutex<Option<dialog::DialogHandler>>>,
+    // every dialog opened so far, for `get_dialogs`
+    dialogs: Arc<Mutex<Vec<dialog::Dialog>>>,
 }
 
 #[derive(Debug, Fail)]
@@ -113,6 +141,155 @@ pub struct NavigationFailed {
     error_text: String,
 }
 
+/// The point in the page's lifecycle at which a navigation counts as finished.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaitUntil {
+    /// The `load` event has fired.
+    Load,
+    /// The `DOMContentLoaded` event has fired.
+    DomContentLoaded,
+    /// There have been no network connections for at least 500 ms.
+    NetworkIdle0,
+    /// There have been no more than 2 network connections for at least 500 ms.
+    NetworkIdle2,
+}
+
+impl WaitUntil {
+    // the name of the corresponding `Page.lifecycleEvent`
+    fn lifecycle_event_name(self) -> &'static str {
+        match self {
+            Self::Load => "load",
+            Self::DomContentLoaded => "DOMContentLoaded",
+            Self::NetworkIdle0 => "networkIdle",
+            Self::NetworkIdle2 => "networkAlmostIdle",
+        }
+    }
+}
+
+/// How often the predicate passed to
+/// [`Tab::wait_for_function`](struct.Tab.html#method.wait_for_function) is checked.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Polling {
+    /// On every animation frame, via `requestAnimationFrame`.
+    Raf,
+    /// Whenever the DOM changes, via a `MutationObserver`.
+    Mutation,
+    /// Every so many milliseconds.
+    Interval(u64),
+}
+
+/// Options for [`Tab::wait_for_function`](struct.Tab.html#method.wait_for_function).
+#[derive(Debug, Clone, Copy)]
+pub struct WaitForFunctionOptions {
+    /// defaults to `Polling::Raf`
+    pub polling: Polling,
+    /// How long to wait for the predicate to return something truthy.
+    ///
+    /// defaults to None, which means the tab's default timeout
+    pub timeout: Option<Duration>,
+}
+
+impl Default for WaitForFunctionOptions {
+    fn default() -> Self {
+        Self {
+            polling: Polling::Raf,
+            timeout: None,
+        }
+    }
+}
+
+// Resolves to `{ done: true, value }` once the predicate returns something truthy, or to
+// `{ done: false }` after `timeout` milliseconds. Takes the predicate, its arguments, the
+// polling mode and the timeout, in that order.
+const WAIT_FOR_FUNCTION_JS: &str = r#"
+(predicate, args, polling, timeout) => new Promise((resolve, reject) => {
+    let settled = false;
+    let observer = null;
+    const settle = (callback, value) => {
+        if (settled) {
+            return;
+        }
+        settled = true;
+        if (observer) {
+            observer.disconnect();
+        }
+        callback(value);
+    };
+    const check = async () => {
+        if (settled) {
+            return true;
+        }
+        try {
+            const value = await predicate(...args);
+            if (value) {
+                settle(resolve, { done: true, value });
+            }
+        } catch (error) {
+            settle(reject, error);
+        }
+        return settled;
+    };
+
+    setTimeout(() => settle(resolve, { done: false }), timeout);
+    if (polling === "raf") {
+        const onFrame = async () => {
+            if (!(await check())) {
+                requestAnimationFrame(onFrame);
+            }
+        };
+        onFrame();
+    } else if (polling === "mutation") {
+        check().then(done => {
+            if (!done) {
+                observer = new MutationObserver(check);
+                observer.observe(document, {
+                    childList: true,
+                    subtree: true,
+                    attributes: true,
+                    characterData: true,
+                });
+            }
+        });
+    } else {
+        const onInterval = async () => {
+            if (!(await check())) {
+                setTimeout(onInterval, polling);
+            }
+        };
+        onInterval();
+    }
+})
+"#;
+
+// calls to the browser time out after 15 seconds, so longer waits are split into slices which
+// each re-run the predicate from scratch
+const WAIT_FOR_FUNCTION_SLICE: Duration = Duration::from_secs(10);
+
+/// Options for [`Tab::navigate_with`](struct.Tab.html#method.navigate_with).
+#[derive(Debug, Clone)]
+pub struct NavigateOptions {
+    /// defaults to `WaitUntil::Load`
+    pub wait_until: WaitUntil,
+    /// How long to wait for the navigation to finish.
+    ///
+    /// defaults to 20 seconds
+    pub timeout: Duration,
+    /// Sent as the `Referer` header of the request for the page.
+    ///
+    /// defaults to None
+    pub referrer: Option<String>,
+}
+
+impl Default for NavigateOptions {
+    fn default() -> Self {
+        Self {
+            wait_until: WaitUntil::Load,
+            timeout: Duration::from_secs(20),
+            referrer: None,
+        }
+    }
+}
+
 impl NoElementFound {
     pub fn map(error: Error) -> Error {
         match error.downcast::<RemoteError>() {
@@ -132,6 +309,23 @@ impl NoElementFound {
     }
 }
 
+// inserts a `<base>` element at the start of `html`, but after the doctype if there is one, as
+// anything in front of that would put the page in quirks mode
+fn with_base_url(html: &str, base_url: &str) -> String {
+    let base = format!(
+        r#"<base href="{}">"#,
+        base_url.replace('&', "&amp;").replace('"', "&quot;")
+    );
+    let trimmed = html.trim_start();
+    if trimmed.to_lowercase().starts_with("<!doctype") {
+        if let Some(end) = trimmed.find('>') {
+            let (doctype, rest) = trimmed.split_at(end + 1);
+            return format!("{}{}{}", doctype, base, rest);
+        }
+    }
+    format!("{}{}", base, html)
+}
+
 impl Tab {
     pub fn new(target_info: TargetInfo, transport: Arc<Transport>) -> Fallible<Self> {
         let target_id = target_info.target_id.clone();
@@ -153,6 +347,7 @@ impl Tab {
             transport,
             session_id,
             navigating: Arc::new(AtomicBool::new(false)),
+            network_enabled: Arc::new(AtomicBool::new(false)),
             target_info: target_info_mutex,
             request_interceptor: Arc::new(Mutex::new(Arc::new(
                 |_transport, _session_id, _interception| RequestPausedDecision::Continue(None),
@@ -164,7 +359,12 @@ impl Tab {
             })),
             default_timeout: Arc::new(RwLock::new(Duration::from_secs(3))),
             event_listeners: Arc::new(Mutex::new(Vec::new())),
+            execution_contexts: Arc::new(Mutex::new(HashMap::new())),
             slow_motion_multiplier: Arc::new(RwLock::new(0.0)),
+            mouse_state: Arc::new(Mutex::new(mouse::MouseState::default())),
+            modifiers: Arc::new(Mutex::new(mouse::Modifiers::NONE)),
+            dialog_handler: Arc::new(Mutex::new(None)),
+            dialogs: Arc::new(Mutex::new(Vec::new())),
         };
 
         tab.call_method(page::methods::Enable {})?;
@@ -217,6 +417,85 @@ impl Tab {
         .map(|_| ())
     }
 
+    /// Overrides the position reported by the Geolocation API.
+    ///
+    /// The page will still need the `Geolocation` permission; see `Browser::grant_permissions`.
+    pub fn set_geolocation(&self, latitude: f64, longitude: f64, accuracy: f64) -> Fallible<&Self> {
+        self.call_method(emulation::methods::SetGeolocationOverride {
+            latitude: Some(latitude),
+            longitude: Some(longitude),
+            accuracy: Some(accuracy),
+        })?;
+        Ok(self)
+    }
+
+    /// Makes pages believe there's a touchscreen, so they get touch events from
+    /// [`Touchscreen`](touchscreen/struct.Touchscreen.html) and `Element::tap`, and see
+    /// `ontouchstart` and `navigator.maxTouchPoints` like on a phone.
+    pub fn set_touch_emulation_enabled(&self, enabled: bool) -> Fallible<&Self> {
+        self.call_method(emulation::methods::SetTouchEmulationEnabled {
+            enabled,
+            max_touch_points: if enabled { Some(5) } else { None },
+        })?;
+        Ok(self)
+    }
+
+    /// Makes the tab look like `device` to pages: sets the size of the viewport, the device
+    /// pixel ratio (which screenshots are taken at), whether the page gets a mobile layout, touch
+    /// support and the user agent.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// # use headless_chrome::protocol::page::ScreenshotFormat;
+    /// use headless_chrome::browser::tab::device::{self, Device};
+    ///
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.emulate(&device::IPHONE_X.landscape())?;
+    /// tab.emulate(Device::find("Pixel 2").unwrap())?;
+    /// tab.navigate_to("https://www.wikipedia.org")?.wait_until_navigated()?;
+    /// let retina_png = tab.capture_screenshot(ScreenshotFormat::PNG, None, true)?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn emulate(&self, device: &device::Device) -> Fallible<&Self> {
+        debug!("Emulating {}", device.name);
+        let viewport = &device.viewport;
+        let screen_orientation = if viewport.is_landscape {
+            emulation::ScreenOrientation {
+                orientation_type: emulation::ScreenOrientationType::LandscapePrimary,
+                angle: 90,
+            }
+        } else {
+            emulation::ScreenOrientation {
+                orientation_type: emulation::ScreenOrientationType::PortraitPrimary,
+                angle: 0,
+            }
+        };
+        self.call_method(emulation::methods::SetDeviceMetricsOverride {
+            width: viewport.width,
+            height: viewport.height,
+            device_scale_factor: viewport.device_scale_factor,
+            mobile: viewport.is_mobile,
+            screen_orientation: Some(screen_orientation),
+        })?;
+        self.set_touch_emulation_enabled(viewport.has_touch)?;
+        if let Some(user_agent) = device.user_agent {
+            self.set_user_agent(user_agent, None, None)?;
+        }
+        Ok(self)
+    }
+
+    /// Undoes `emulate`, except for the user agent, going back to the size of the window.
+    pub fn clear_device_emulation(&self) -> Fallible<&Self> {
+        self.call_method(emulation::methods::ClearDeviceMetricsOverride {})?;
+        self.set_touch_emulation_enabled(false)?;
+        Ok(self)
+    }
+
     fn start_event_handler_thread(&self) {
         let transport: Arc<Transport> = Arc::clone(&self.transport);
         let incoming_events_rx = self
@@ -228,6 +507,9 @@ impl Tab {
         let auth_handler_mutex = self.auth_handler.clone();
         let session_id = self.session_id.clone();
         let listeners_mutex = Arc::clone(&self.event_listeners);
+        let execution_contexts_mutex = Arc::clone(&self.execution_contexts);
+        let dialog_handler_mutex = Arc::clone(&self.dialog_handler);
+        let dialogs_mutex = Arc::clone(&self.dialogs);
 
         thread::spawn(move || {
             for event in incoming_events_rx {
@@ -299,6 +581,23 @@ impl Tab {
                             warn!("Tried to handle request after connection was closed");
                         }
                     }
+                    Event::JavascriptDialogOpening(event) => {
+                        let dialog = event.params;
+                        debug!("Dialog opened: {:?}", dialog);
+                        dialogs_mutex.lock().unwrap().push(dialog.clone());
+                        if let Some(handler) = dialog_handler_mutex.lock().unwrap().as_ref() {
+                            let response = handler.respond(&dialog);
+                            let method = page::methods::HandleJavaScriptDialog {
+                                accept: response.accept,
+                                prompt_text: response.prompt_text.as_ref().map(String::as_str),
+                            };
+                            let result =
+                                transport.call_method_on_target(session_id.clone(), method);
+                            if result.is_err() {
+                                warn!("Tried to handle dialog after connection was closed");
+                            }
+                        }
+                    }
                     Event::ResponseReceived(ev) => {
                         if let Some(handler) = response_handler_mutex.lock().unwrap().as_ref() {
                             let request_id = ev.params.request_id.clone();
@@ -311,6 +610,26 @@ impl Tab {
                             handler(ev.params, &retrieve_body);
                         }
                     }
+                    Event::ExecutionContextCreated(event) => {
+                        let context = event.params.context;
+                        if let Some(aux_data) = context.aux_data {
+                            if let (Some(frame_id), Some(true)) =
+                                (aux_data.frame_id, aux_data.is_default)
+                            {
+                                let mut execution_contexts =
+                                    execution_contexts_mutex.lock().unwrap();
+                                execution_contexts.insert(frame_id, context.id);
+                            }
+                        }
+                    }
+                    Event::ExecutionContextDestroyed(event) => {
+                        let context_id = event.params.execution_context_id;
+                        let mut execution_contexts = execution_contexts_mutex.lock().unwrap();
+                        execution_contexts.retain(|_, id| *id != context_id);
+                    }
+                    Event::ExecutionContextsCleared(_) => {
+                        execution_contexts_mutex.lock().unwrap().clear();
+                    }
                     _ => {
                         let mut raw_event = format!("{:?}", event);
                         raw_event.truncate(50);
@@ -352,7 +671,10 @@ impl Tab {
     }
 
     pub fn navigate_to(&self, url: &str) -> Fallible<&Self> {
-        let return_object = self.call_method(Navigate { url })?;
+        let return_object = self.call_method(Navigate {
+            url,
+            referrer: None,
+        })?;
         if let Some(error_text) = return_object.error_text {
             return Err(NavigationFailed { error_text }.into());
         }
@@ -365,6 +687,402 @@ impl Tab {
         Ok(self)
     }
 
+    /// Navigates to `url` and waits until the page reaches `options.wait_until`.
+    ///
+    /// Returns the response the page was loaded from, after any redirects, so its status can be
+    /// checked. Navigations within the same document (e.g. to an anchor) don't load anything, in
+    /// which case this returns `None`.
+    ///
+    /// ```rust
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::browser::tab::{NavigateOptions, WaitUntil};
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// let response = tab.navigate_with(
+    ///     "https://example.com",
+    ///     NavigateOptions {
+    ///         wait_until: WaitUntil::NetworkIdle0,
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// assert_eq!(response.unwrap().status, 200);
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn navigate_with(
+        &self,
+        url: &str,
+        options: NavigateOptions,
+    ) -> Fallible<Option<network::Response>> {
+        self.watch_navigation(|events| self.navigate_and_wait(url, &options, events))
+    }
+
+    /// Runs `action` and waits for the navigation of the main frame it triggers to reach
+    /// `options.wait_until`, returning the main document's response like
+    /// [`navigate_with`](#method.navigate_with) does. `options.referrer` is ignored.
+    ///
+    /// Unlike calling `wait_until_navigated` after e.g. clicking a link, this can't miss a
+    /// navigation which starts (or even finishes) before the wait does, because the events are
+    /// already being listened to when `action` runs.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::browser::tab::NavigateOptions;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// let link = tab.find_element("a")?;
+    /// let response = tab.expect_navigation(NavigateOptions::default(), || link.click())?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn expect_navigation<F, T>(
+        &self,
+        options: NavigateOptions,
+        action: F,
+    ) -> Fallible<Option<network::Response>>
+    where
+        F: FnOnce() -> Fallible<T>,
+    {
+        let main_frame_id = self.main_frame()?.info.id;
+
+        self.watch_navigation(|events| {
+            let start = std::time::Instant::now();
+            action()?;
+
+            let loader_id = util::Wait::with_timeout(options.timeout).until(|| {
+                let events = events.lock().unwrap();
+                events.iter().rev().find_map(|event| match event {
+                    Event::FrameNavigated(event) if event.params.frame.id == main_frame_id => {
+                        Some(event.params.frame.loader_id.clone())
+                    }
+                    _ => None,
+                })
+            })?;
+            debug!("Main frame navigated, waiting for {:?}", options.wait_until);
+
+            let remaining = options
+                .timeout
+                .checked_sub(start.elapsed())
+                .unwrap_or_default();
+            self.wait_for_lifecycle_event(
+                &main_frame_id,
+                &loader_id,
+                options.wait_until,
+                remaining,
+                events,
+            )
+        })
+    }
+
+    // runs `f` with the lifecycle, navigation and response events collected while it runs
+    fn watch_navigation<F, T>(&self, f: F) -> Fallible<T>
+    where
+        F: FnOnce(&Mutex<Vec<Event>>) -> Fallible<T>,
+    {
+        // the main response is only reported with the Network domain enabled
+        if !self.network_enabled.load(Ordering::SeqCst) {
+            self.call_method(network::methods::Enable {})?;
+            self.network_enabled.store(true, Ordering::SeqCst);
+        }
+
+        let events = Arc::new(Mutex::new(Vec::new()));
+        let events_clone = Arc::clone(&events);
+        let listener = self.add_event_listener(Arc::new(move |event: &Event| match event {
+            Event::Lifecycle(_) | Event::FrameNavigated(_) | Event::ResponseReceived(_) => {
+                events_clone.lock().unwrap().push(event.clone());
+            }
+            _ => {}
+        }))?;
+
+        let result = f(&events);
+        self.remove_event_listener(&listener)?;
+        result
+    }
+
+    fn navigate_and_wait(
+        &self,
+        url: &str,
+        options: &NavigateOptions,
+        events: &Mutex<Vec<Event>>,
+    ) -> Fallible<Option<network::Response>> {
+        // events for the new document can arrive before Page.navigate returns, which is why
+        // they're already being collected before we know which loader they have to belong to
+        let return_object = self.call_method(Navigate {
+            url,
+            referrer: options.referrer.as_ref().map(String::as_str),
+        })?;
+        if let Some(error_text) = return_object.error_text {
+            return Err(NavigationFailed { error_text }.into());
+        }
+        let loader_id = match return_object.loader_id {
+            Some(loader_id) => loader_id,
+            None => return Ok(None),
+        };
+
+        // the lifecycle events are waited for right here, so the `navigating` flag (which only
+        // `networkAlmostIdle` clears, and which may have already fired) is left alone
+        info!("Navigating a tab to {}", url);
+
+        self.wait_for_lifecycle_event(
+            &return_object.frame_id,
+            &loader_id,
+            options.wait_until,
+            options.timeout,
+            events,
+        )
+    }
+
+    // waits for the document loaded by `loader_id` to reach `wait_until` and returns the response
+    // it was loaded from
+    fn wait_for_lifecycle_event(
+        &self,
+        frame_id: &str,
+        loader_id: &str,
+        wait_until: WaitUntil,
+        timeout: Duration,
+        events: &Mutex<Vec<Event>>,
+    ) -> Fallible<Option<network::Response>> {
+        let event_name = wait_until.lifecycle_event_name();
+        let response = util::Wait::with_timeout(timeout).until(|| {
+            let events = events.lock().unwrap();
+            let reached = events.iter().any(|event| match event {
+                Event::Lifecycle(event) => {
+                    event.params.frame_id == frame_id
+                        && event.params.loader_id == loader_id
+                        && event.params.name == event_name
+                }
+                _ => false,
+            });
+            if !reached {
+                return None;
+            }
+            Some(
+                events
+                    .iter()
+                    .filter_map(|event| match event {
+                        Event::ResponseReceived(event)
+                            if event.params.loader_id == loader_id
+                                && event.params._type == ResourceType::Document
+                                && event.params.frame_id.as_ref().map(String::as_str)
+                                    == Some(frame_id) =>
+                        {
+                            Some(event.params.response.clone())
+                        }
+                        _ => None,
+                    })
+                    .last(),
+            )
+        })?;
+        debug!("Frame {} reached {}", frame_id, event_name);
+
+        Ok(response)
+    }
+
+    /// Waits until `function_declaration`, called with `args`, returns something truthy, and
+    /// returns that value.
+    ///
+    /// The function runs inside the page, so waiting on e.g. an app signalling that it's ready
+    /// doesn't need any polling from Rust. It can also return a promise. If it throws, the wait
+    /// is aborted with that error; if it doesn't return anything truthy in time, this fails with
+    /// `util::Timeout`.
+    ///
+    /// ```rust
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::browser::tab::{Polling, WaitForFunctionOptions};
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.evaluate("setTimeout(() => window.__APP_READY__ = true, 100)", false)?;
+    /// let ready = tab.wait_for_function(
+    ///     "() => window.__APP_READY__",
+    ///     &[],
+    ///     WaitForFunctionOptions {
+    ///         polling: Polling::Interval(50),
+    ///         ..Default::default()
+    ///     },
+    /// )?;
+    /// assert_eq!(ready, serde_json::Value::Bool(true));
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn wait_for_function(
+        &self,
+        function_declaration: &str,
+        args: &[serde_json::Value],
+        options: WaitForFunctionOptions,
+    ) -> Fallible<serde_json::Value> {
+        let timeout = options
+            .timeout
+            .unwrap_or_else(|| *self.default_timeout.read().unwrap());
+        let polling = match options.polling {
+            Polling::Raf => serde_json::json!("raf"),
+            Polling::Mutation => serde_json::json!("mutation"),
+            Polling::Interval(millis) => serde_json::json!(millis),
+        };
+        let args = serde_json::to_string(args)?;
+
+        let start = std::time::Instant::now();
+        loop {
+            let remaining = match timeout.checked_sub(start.elapsed()) {
+                Some(remaining) if remaining > Duration::from_millis(0) => remaining,
+                _ => return Err(util::Timeout.into()),
+            };
+            let slice = std::cmp::min(remaining, WAIT_FOR_FUNCTION_SLICE);
+            let expression = format!(
+                "({})({}, {}, {}, {})",
+                WAIT_FOR_FUNCTION_JS,
+                function_declaration,
+                args,
+                polling,
+                slice.as_millis()
+            );
+
+            let return_object = self.call_method(protocol::runtime::methods::Evaluate {
+                expression: &expression,
+                return_by_value: true,
+                generate_preview: false,
+                silent: false,
+                await_promise: true,
+                include_command_line_api: false,
+                user_gesture: false,
+                context_id: None,
+            })?;
+            if let Some(exception_details) = return_object.exception_details {
+                let description = exception_details
+                    .exception
+                    .and_then(|exception| exception.description)
+                    .unwrap_or(exception_details.text);
+                return Err(format_err!(
+                    "Function passed to wait_for_function threw: {}",
+                    description
+                ));
+            }
+
+            let mut outcome = return_object.result.value.unwrap_or_default();
+            if outcome["done"] == serde_json::Value::Bool(true) {
+                return Ok(outcome["value"].take());
+            }
+        }
+    }
+
+    /// Replaces the page's document with `html`, and waits for it and its resources to load, for
+    /// as long as a navigation would by default.
+    ///
+    /// Relative URLs in `html` are resolved against `base_url` if one is given (by adding a
+    /// `<base>` element to it), and otherwise against the URL of the page that was loaded before.
+    ///
+    /// ```rust
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.set_content(
+    ///     r#"<h1>Invoice</h1><img src="logo.png">"#,
+    ///     Some("https://example.com/assets/"),
+    /// )?;
+    /// assert_eq!(tab.find_element("h1")?.get_inner_text()?, "Invoice");
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn set_content(&self, html: &str, base_url: Option<&str>) -> Fallible<&Self> {
+        self.set_content_with(html, base_url, NavigateOptions::default().timeout)
+    }
+
+    /// Like [`set_content`](#method.set_content), but waits at most `timeout` for the document
+    /// and its resources to load.
+    pub fn set_content_with(
+        &self,
+        html: &str,
+        base_url: Option<&str>,
+        timeout: Duration,
+    ) -> Fallible<&Self> {
+        let html = match base_url {
+            Some(base_url) => with_base_url(html, base_url),
+            None => html.to_string(),
+        };
+        let frame_id = self.main_frame()?.info.id;
+        self.call_method(page::methods::SetDocumentContent {
+            frame_id: &frame_id,
+            html: &html,
+        })?;
+
+        self.wait_for_function(
+            "() => document.readyState === 'complete'",
+            &[],
+            WaitForFunctionOptions {
+                polling: Polling::Interval(10),
+                timeout: Some(timeout),
+            },
+        )?;
+        Ok(self)
+    }
+
+    /// Returns the tab's session history, i.e. the entries the back and forward buttons step
+    /// through.
+    pub fn get_navigation_history(&self) -> Fallible<page::NavigationHistory> {
+        self.call_method(page::methods::GetNavigationHistory {})
+    }
+
+    /// Navigates to the previous entry in the tab's history, like pressing the back button.
+    ///
+    /// Like `navigate_to`, this doesn't wait for the page to load; follow it up with
+    /// `wait_until_navigated`.
+    pub fn go_back(&self) -> Fallible<&Self> {
+        self.navigate_history(-1)
+    }
+
+    /// Navigates to the next entry in the tab's history, like pressing the forward button.
+    ///
+    /// Like `navigate_to`, this doesn't wait for the page to load; follow it up with
+    /// `wait_until_navigated`.
+    pub fn go_forward(&self) -> Fallible<&Self> {
+        self.navigate_history(1)
+    }
+
+    fn navigate_history(&self, delta: JsInt) -> Fallible<&Self> {
+        let history = self.get_navigation_history()?;
+        let entry = |index: JsInt| {
+            usize::try_from(index)
+                .ok()
+                .and_then(|index| history.entries.get(index))
+        };
+        let current = entry(history.current_index);
+        let target = entry(history.current_index + delta).ok_or_else(|| {
+            format_err!(
+                "There is no history entry to go {} to",
+                if delta < 0 { "back" } else { "forward" }
+            )
+        })?;
+
+        self.call_method(page::methods::NavigateToHistoryEntry {
+            entry_id: target.id,
+        })?;
+
+        // stepping between entries which only differ in their fragment doesn't load anything, so
+        // there won't be any lifecycle events to wait for
+        let document_url = |url: &str| url.split('#').next().unwrap_or_default().to_string();
+        let same_document = current.map_or(false, |current| {
+            document_url(&current.url) == document_url(&target.url)
+        });
+        if !same_document {
+            self.navigating.store(true, Ordering::SeqCst);
+        }
+
+        info!("Navigating a tab through its history to {}", target.url);
+
+        Ok(self)
+    }
+
     /// Set default timeout for the tab
     ///
     /// This will be applied to all [wait_for_element](Tab::wait_for_element) and [wait_for_elements](Tab::wait_for_elements) calls for this tab
@@ -420,33 +1138,36 @@ impl Tab {
         sleep(Duration::from_millis(scaled_millis));
     }
 
-    pub fn wait_for_element(&self, selector: &str) -> Fallible<Element<'_>> {
+    pub fn wait_for_element<S: Into<Selector>>(&self, selector: S) -> Fallible<Element<'_>> {
         self.wait_for_element_with_custom_timeout(selector, *self.default_timeout.read().unwrap())
     }
 
-    pub fn wait_for_element_with_custom_timeout(
+    pub fn wait_for_element_with_custom_timeout<S: Into<Selector>>(
         &self,
-        selector: &str,
+        selector: S,
         timeout: std::time::Duration,
     ) -> Fallible<Element<'_>> {
+        let selector = selector.into();
         debug!("Waiting for element with selector: {}", selector);
         util::Wait::with_timeout(timeout).strict_until(
-            || self.find_element(selector),
+            || self.find_element(&selector),
             Error::downcast::<NoElementFound>,
         )
     }
 
-    pub fn wait_for_elements(&self, selector: &str) -> Fallible<Vec<Element<'_>>> {
+    pub fn wait_for_elements<S: Into<Selector>>(&self, selector: S) -> Fallible<Vec<Element<'_>>> {
+        let selector = selector.into();
         debug!("Waiting for element with selector: {}", selector);
         util::Wait::with_timeout(*self.default_timeout.read().unwrap()).strict_until(
-            || self.find_elements(selector),
+            || self.find_elements(&selector),
             Error::downcast::<NoElementFound>,
         )
     }
 
-    /// Returns the first element in the document which matches the given CSS selector.
+    /// Returns the first element in the document which matches the given selector: CSS unless
+    /// it says otherwise, see [`Selector`](selector/enum.Selector.html).
     ///
-    /// Equivalent to the following JS:
+    /// For a CSS selector, equivalent to the following JS:
     ///
     /// ```js
     /// document.querySelector(selector)
@@ -475,22 +1196,34 @@ impl Tab {
     /// # Ok(())
     /// # }
     /// ```
-    pub fn find_element(&self, selector: &str) -> Fallible<Element<'_>> {
+    pub fn find_element<S: Into<Selector>>(&self, selector: S) -> Fallible<Element<'_>> {
+        let selector = selector.into();
         trace!("Looking up element via selector: {}", selector);
 
         let root_node_id = self.get_document()?.node_id;
         self.run_query_selector_on_node(root_node_id, selector)
     }
 
-    pub fn run_query_selector_on_node(
+    pub fn run_query_selector_on_node<S: Into<Selector>>(
         &self,
         node_id: NodeId,
-        selector: &str,
+        selector: S,
     ) -> Fallible<Element<'_>> {
-        let node_id = self
-            .call_method(dom::methods::QuerySelector { node_id, selector })
-            .map_err(NoElementFound::map)?
-            .node_id;
+        let node_id = match selector.into() {
+            Selector::Css(selector) => {
+                self.call_method(dom::methods::QuerySelector {
+                    node_id,
+                    selector: &selector,
+                })
+                .map_err(NoElementFound::map)?
+                .node_id
+            }
+            selector => self
+                .query_selector_all(node_id, &selector)?
+                .into_iter()
+                .next()
+                .ok_or(NoElementFound {})?,
+        };
 
         Element::new(&self, node_id)
     }
@@ -504,17 +1237,20 @@ impl Tab {
             .root)
     }
 
-    pub fn find_elements(&self, selector: &str) -> Fallible<Vec<Element<'_>>> {
+    pub fn find_elements<S: Into<Selector>>(&self, selector: S) -> Fallible<Vec<Element<'_>>> {
+        let selector = selector.into();
         trace!("Looking up elements via selector: {}", selector);
 
         let root_node_id = self.get_document()?.node_id;
-        let node_ids = self
-            .call_method(dom::methods::QuerySelectorAll {
-                node_id: root_node_id,
-                selector,
-            })
-            .map_err(NoElementFound::map)?
-            .node_ids;
+        self.run_query_selector_all_on_node(root_node_id, selector)
+    }
+
+    pub fn run_query_selector_all_on_node<S: Into<Selector>>(
+        &self,
+        node_id: NodeId,
+        selector: S,
+    ) -> Fallible<Vec<Element<'_>>> {
+        let node_ids = self.query_selector_all(node_id, &selector.into())?;
 
         if node_ids.is_empty() {
             return Err(NoElementFound {}.into());
@@ -532,62 +1268,21 @@ impl Tab {
                 node_id: Some(node_id),
                 backend_node_id: None,
                 depth: Some(100),
+                pierce: None,
             })?
             .node;
         Ok(node)
     }
 
+    /// Types `string_to_type` into the focused element, see `Keyboard::type_str`.
     pub fn type_str(&self, string_to_type: &str) -> Fallible<&Self> {
-        for c in string_to_type.split("") {
-            // split call above will have empty string at start and end which we won't type
-            if c == "" {
-                continue;
-            }
-            self.press_key(c)?;
-        }
+        self.keyboard().type_str(string_to_type)?;
         Ok(self)
     }
 
+    /// Presses a key, or a chord like `"Control+A"`, see `Keyboard::press`.
     pub fn press_key(&self, key: &str) -> Fallible<&Self> {
-        let definition = keys::get_key_definition(key)?;
-
-        // See https://github.com/GoogleChrome/puppeteer/blob/62da2366c65b335751896afbb0206f23c61436f1/lib/Input.js#L114-L115
-        let text = definition.text.or_else(|| {
-            if definition.key.len() == 1 {
-                Some(definition.key)
-            } else {
-                None
-            }
-        });
-
-        // See https://github.com/GoogleChrome/puppeteer/blob/62da2366c65b335751896afbb0206f23c61436f1/lib/Input.js#L52
-        let key_down_event_type = if text.is_some() {
-            "keyDown"
-        } else {
-            "rawKeyDown"
-        };
-
-        let key = Some(definition.key);
-        let code = Some(definition.code);
-
-        self.optional_slow_motion_sleep(25);
-
-        self.call_method(input::methods::DispatchKeyEvent {
-            event_type: key_down_event_type,
-            key,
-            text,
-            code: Some(definition.code),
-            windows_virtual_key_code: definition.key_code,
-            native_virtual_key_code: definition.key_code,
-        })?;
-        self.call_method(input::methods::DispatchKeyEvent {
-            event_type: "keyUp",
-            key,
-            text,
-            code,
-            windows_virtual_key_code: definition.key_code,
-            native_virtual_key_code: definition.key_code,
-        })?;
+        self.keyboard().press(key)?;
         Ok(self)
     }
 
@@ -597,14 +1292,7 @@ impl Tab {
             warn!("Midpoint of element shouldn't be 0,0. Something is probably wrong.")
         }
 
-        self.optional_slow_motion_sleep(100);
-
-        self.call_method(input::methods::DispatchMouseEvent {
-            event_type: "mouseMoved",
-            x: point.x,
-            y: point.y,
-            ..Default::default()
-        })?;
+        self.mouse().move_to(point)?;
 
         Ok(self)
     }
@@ -615,23 +1303,7 @@ impl Tab {
             warn!("Midpoint of element shouldn't be 0,0. Something is probably wrong.")
         }
 
-        self.move_mouse_to_point(point)?;
-
-        self.optional_slow_motion_sleep(250);
-        self.call_method(input::methods::DispatchMouseEvent {
-            event_type: "mousePressed",
-            x: point.x,
-            y: point.y,
-            button: Some("left"),
-            click_count: Some(1),
-        })?;
-        self.call_method(input::methods::DispatchMouseEvent {
-            event_type: "mouseReleased",
-            x: point.x,
-            y: point.y,
-            button: Some("left"),
-            click_count: Some(1),
-        })?;
+        self.mouse().click(point, MouseButton::Left)?;
         Ok(self)
     }
 
@@ -912,11 +1584,43 @@ impl Tab {
                 await_promise,
                 include_command_line_api: false,
                 user_gesture: false,
+                context_id: None,
             })?
             .result;
         Ok(result)
     }
 
+    /// Returns all frames of the page, starting with the main frame, in document order.
+    pub fn frames(&self) -> Fallible<Vec<Frame<'_>>> {
+        let frame_tree = self.call_method(page::methods::GetFrameTree {})?.frame_tree;
+
+        let mut frames = Vec::new();
+        let mut pending = vec![frame_tree];
+        while let Some(frame_tree) = pending.pop() {
+            frames.push(Frame::new(frame_tree.frame, self));
+            if let Some(child_frames) = frame_tree.child_frames {
+                pending.extend(child_frames.into_iter().rev());
+            }
+        }
+        Ok(frames)
+    }
+
+    pub fn main_frame(&self) -> Fallible<Frame<'_>> {
+        let frame_tree = self.call_method(page::methods::GetFrameTree {})?.frame_tree;
+        Ok(Frame::new(frame_tree.frame, self))
+    }
+
+    // the main world execution context of a frame, which we only hear about with the Runtime
+    // domain enabled
+    fn execution_context_id(&self, frame_id: &str) -> Fallible<JsInt> {
+        self.call_method(runtime::methods::Enable {})?;
+
+        let execution_contexts = Arc::clone(&self.execution_contexts);
+        let context_id = util::Wait::with_timeout(*self.default_timeout.read().unwrap())
+            .until(|| execution_contexts.lock().unwrap().get(frame_id).cloned())?;
+        Ok(context_id)
+    }
+
     /// Adds event listener to Event
     ///
     /// Make sure you are enabled domain you are listening events to.
@@ -979,6 +1683,9 @@ impl Tab {
     }
 
     /// Tries to close page, running its beforeunload hooks, if any
+    ///
+    /// A page which asks whether to leave it stays open, with the question showing, unless a
+    /// dialog handler (see `set_dialog_handler`) accepts it.
     pub fn close_with_unload(&self) -> Fallible<bool> {
         self.call_method(protocol::page::methods::Close {})
             .map(|_| true)
@@ -1113,4 +1820,59 @@ impl Tab {
         self.call_method(SetExtraHTTPHeaders { headers })?;
         Ok(())
     }
+
+    /// Throttles the tab's network traffic, or cuts it off, e.g. to see how a page copes with
+    /// slow connections. `NetworkConditions::NO_THROTTLING` turns this off again.
+    ///
+    /// ```rust,no_run
+    /// # use failure::Fallible;
+    /// # fn main() -> Fallible<()> {
+    /// # use headless_chrome::Browser;
+    /// use headless_chrome::protocol::network::NetworkConditions;
+    ///
+    /// # let browser = Browser::default()?;
+    /// let tab = browser.wait_for_initial_tab()?;
+    /// tab.emulate_network_conditions(NetworkConditions::SLOW_3G)?;
+    /// tab.emulate_network_conditions(NetworkConditions {
+    ///     latency: 5000.0,
+    ///     ..NetworkConditions::NO_THROTTLING
+    /// })?;
+    /// #
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn emulate_network_conditions(&self, conditions: NetworkConditions) -> Fallible<&Self> {
+        self.call_method(network::methods::Enable {})?;
+        self.call_method(network::methods::EmulateNetworkConditions {
+            offline: conditions.offline,
+            latency: conditions.latency,
+            download_throughput: conditions.download_throughput,
+            upload_throughput: conditions.upload_throughput,
+        })?;
+        Ok(self)
+    }
+
+    /// Slows the tab's CPU down by `rate`, e.g. 4 to make it four times slower, the way a
+    /// cheap phone would be. 1 turns this off again.
+    pub fn set_cpu_throttling_rate(&self, rate: f64) -> Fallible<&Self> {
+        self.call_method(emulation::methods::SetCPUThrottlingRate { rate })?;
+        Ok(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::with_base_url;
+
+    #[test]
+    fn base_url_goes_after_doctype() {
+        assert_eq!(
+            with_base_url("<p>hi</p>", "http://a.test/?x=1&y=\"2\""),
+            r#"<base href="http://a.test/?x=1&amp;y=&quot;2&quot;"><p>hi</p>"#
+        );
+        assert_eq!(
+            with_base_url("\n<!DOCTYPE html><p>hi</p>", "http://a.test/"),
+            r#"<!DOCTYPE html><base href="http://a.test/"><p>hi</p>"#
+        );
+    }
 }
@@ -0,0 +1,280 @@
+use std::ops::BitOr;
+
+use failure::Fallible;
+use log::*;
+
+use crate::browser::tab::point::Point;
+use crate::browser::tab::Tab;
+use crate::protocol::input;
+use crate::protocol::types::JsUInt;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    Back,
+    Forward,
+}
+
+impl MouseButton {
+    fn name(self) -> &'static str {
+        match self {
+            Self::Left => "left",
+            Self::Right => "right",
+            Self::Middle => "middle",
+            Self::Back => "back",
+            Self::Forward => "forward",
+        }
+    }
+
+    // the button's bit in the `buttons` field of mouse events
+    fn bit(self) -> JsUInt {
+        match self {
+            Self::Left => 1,
+            Self::Right => 2,
+            Self::Middle => 4,
+            Self::Back => 8,
+            Self::Forward => 16,
+        }
+    }
+}
+
+/// Modifier keys to hold down during mouse events, combined with `|`:
+///
+/// ```rust
+/// # use headless_chrome::browser::tab::mouse::Modifiers;
+/// let modifiers = Modifiers::CTRL | Modifiers::SHIFT;
+/// assert!(modifiers.contains(Modifiers::SHIFT));
+/// assert!(!modifiers.contains(Modifiers::ALT));
+/// ```
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Modifiers(JsUInt);
+
+impl Modifiers {
+    pub const NONE: Self = Self(0);
+    pub const ALT: Self = Self(1);
+    pub const CTRL: Self = Self(2);
+    /// The Command key on macOS, the Windows key elsewhere.
+    pub const META: Self = Self(4);
+    pub const SHIFT: Self = Self(8);
+
+    pub fn contains(self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub(crate) fn bits(self) -> JsUInt {
+        self.0
+    }
+
+    pub(crate) fn without(self, other: Self) -> Self {
+        Self(self.0 & !other.0)
+    }
+}
+
+impl BitOr for Modifiers {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self {
+        Self(self.0 | other.0)
+    }
+}
+
+// where a tab's mouse is and which of its buttons are held down, which every event has to report
+#[derive(Debug)]
+pub(crate) struct MouseState {
+    position: Point,
+    buttons: JsUInt,
+}
+
+impl Default for MouseState {
+    fn default() -> Self {
+        Self {
+            position: Point { x: 0.0, y: 0.0 },
+            buttons: 0,
+        }
+    }
+}
+
+/// The mouse of a tab, for when `Element::click` and friends aren't enough.
+///
+/// The mouse remembers where it is and which buttons are held down, so for example a drag is
+/// `down`, `move_to` and `up`:
+///
+/// ```rust,no_run
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// # use headless_chrome::Browser;
+/// # use headless_chrome::browser::tab::mouse::MouseButton;
+/// # use headless_chrome::browser::tab::Point;
+/// # let browser = Browser::default()?;
+/// let tab = browser.wait_for_initial_tab()?;
+/// let handle = tab.find_element("input[type=range]")?.get_midpoint()?;
+/// let mouse = tab.mouse();
+/// mouse.move_to(handle)?;
+/// mouse.down(MouseButton::Left)?;
+/// mouse.move_by_steps(handle + Point { x: 50.0, y: 0.0 }, 10)?;
+/// mouse.up(MouseButton::Left)?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub struct Mouse<'a> {
+    tab: &'a Tab,
+}
+
+impl<'a> Mouse<'a> {
+    pub fn new(tab: &'a Tab) -> Self {
+        Self { tab }
+    }
+
+    /// Where the mouse was last moved to.
+    pub fn position(&self) -> Point {
+        self.tab.mouse_state.lock().unwrap().position
+    }
+
+    /// Sets the modifier keys reported as held down by the mouse and keyboard events that
+    /// follow, just like holding them with `Keyboard::down` does.
+    pub fn set_modifiers(&self, modifiers: Modifiers) -> &Self {
+        *self.tab.modifiers.lock().unwrap() = modifiers;
+        self
+    }
+
+    /// Moves the mouse straight to `point`, with a single `mouseMoved` event.
+    pub fn move_to(&self, point: Point) -> Fallible<&Self> {
+        self.move_by_steps(point, 1)
+    }
+
+    /// Moves the mouse to `point` in a straight line, with `steps` evenly spaced `mouseMoved`
+    /// events, which pages reacting to the mouse as it moves (sliders, drag and drop libraries)
+    /// tend to need.
+    pub fn move_by_steps(&self, point: Point, steps: u32) -> Fallible<&Self> {
+        trace!("Moving mouse to {:?} in {} steps", point, steps);
+        self.tab.optional_slow_motion_sleep(100);
+
+        let start = self.position();
+        let steps = steps.max(1);
+        for step in 1..=steps {
+            let progress = f64::from(step) / f64::from(steps);
+            let position = start + (point - start) * progress;
+            self.dispatch(input::methods::DispatchMouseEvent {
+                event_type: "mouseMoved",
+                x: position.x,
+                y: position.y,
+                ..Default::default()
+            })?;
+            self.tab.mouse_state.lock().unwrap().position = position;
+        }
+        Ok(self)
+    }
+
+    /// Presses `button` where the mouse is.
+    pub fn down(&self, button: MouseButton) -> Fallible<&Self> {
+        self.press(button, 1)
+    }
+
+    /// Releases `button` where the mouse is.
+    pub fn up(&self, button: MouseButton) -> Fallible<&Self> {
+        self.release(button, 1)
+    }
+
+    /// Moves the mouse to `point` and presses and releases `button` there.
+    pub fn click(&self, point: Point, button: MouseButton) -> Fallible<&Self> {
+        self.click_times(point, button, 1)
+    }
+
+    pub fn double_click(&self, point: Point) -> Fallible<&Self> {
+        self.click_times(point, MouseButton::Left, 2)
+    }
+
+    /// Clicks three times in a row, which selects a paragraph of text.
+    pub fn triple_click(&self, point: Point) -> Fallible<&Self> {
+        self.click_times(point, MouseButton::Left, 3)
+    }
+
+    /// Clicks with the right button, which opens the page's context menu, if it has one.
+    pub fn right_click(&self, point: Point) -> Fallible<&Self> {
+        self.click_times(point, MouseButton::Right, 1)
+    }
+
+    /// Moves the mouse to `point` and clicks `button` `count` times in a row, so the page sees
+    /// the `click_count` go up as it would with a real mouse, and fires `dblclick` on the second.
+    pub fn click_times(&self, point: Point, button: MouseButton, count: u32) -> Fallible<&Self> {
+        trace!("Clicking {:?} {} times with {:?}", point, count, button);
+        self.move_to(point)?;
+        self.tab.optional_slow_motion_sleep(250);
+        for click_count in 1..=count {
+            self.press(button, click_count)?;
+            self.release(button, click_count)?;
+        }
+        Ok(self)
+    }
+
+    /// Scrolls with the mouse wheel where the mouse is, by `delta_x` and `delta_y` CSS pixels.
+    /// Positive values scroll right and down.
+    pub fn wheel(&self, delta_x: f64, delta_y: f64) -> Fallible<&Self> {
+        let position = self.position();
+        self.dispatch(input::methods::DispatchMouseEvent {
+            event_type: "mouseWheel",
+            x: position.x,
+            y: position.y,
+            delta_x: Some(delta_x),
+            delta_y: Some(delta_y),
+            ..Default::default()
+        })?;
+        Ok(self)
+    }
+
+    fn press(&self, button: MouseButton, click_count: u32) -> Fallible<&Self> {
+        let position = {
+            let mut state = self.tab.mouse_state.lock().unwrap();
+            state.buttons |= button.bit();
+            state.position
+        };
+        self.dispatch(input::methods::DispatchMouseEvent {
+            event_type: "mousePressed",
+            x: position.x,
+            y: position.y,
+            button: Some(button.name()),
+            click_count: Some(click_count.into()),
+            ..Default::default()
+        })?;
+        Ok(self)
+    }
+
+    fn release(&self, button: MouseButton, click_count: u32) -> Fallible<&Self> {
+        let position = {
+            let mut state = self.tab.mouse_state.lock().unwrap();
+            state.buttons &= !button.bit();
+            state.position
+        };
+        self.dispatch(input::methods::DispatchMouseEvent {
+            event_type: "mouseReleased",
+            x: position.x,
+            y: position.y,
+            button: Some(button.name()),
+            click_count: Some(click_count.into()),
+            ..Default::default()
+        })?;
+        Ok(self)
+    }
+
+    // sends `event` along with the buttons and modifiers currently held down
+    fn dispatch(&self, event: input::methods::DispatchMouseEvent) -> Fallible<()> {
+        let buttons = self.tab.mouse_state.lock().unwrap().buttons;
+        let modifiers = *self.tab.modifiers.lock().unwrap();
+        self.tab.call_method(input::methods::DispatchMouseEvent {
+            buttons: Some(buttons),
+            modifiers: Some(modifiers.bits()),
+            ..event
+        })?;
+        Ok(())
+    }
+}
+
+impl Tab {
+    /// Returns the tab's [`Mouse`](mouse/struct.Mouse.html).
+    pub fn mouse(&self) -> Mouse<'_> {
+        Mouse::new(self)
+    }
+}
@@ -0,0 +1,374 @@
+use std::fmt;
+
+use failure::{format_err, Fallible};
+
+use crate::browser::tab::{NoElementFound, Tab};
+use crate::protocol::dom::{self, Node, NodeId, ShadowRootType};
+use crate::protocol::runtime;
+
+/// What to look for with `Tab::find_element` and friends, and how.
+///
+/// Strings convert into selectors, using a prefix to pick the engine:
+///
+/// * `css=div.item`, or no prefix at all: a CSS selector, as before.
+/// * `xpath=//button[@type="submit"]`, or anything starting with `//` or `..`: an XPath expression,
+///   evaluated relative to the element it's run on. Below an element, expressions starting with
+///   `/` or `//` are scoped to it too, so `//li` only finds the `<li>`s inside it. Expressions
+///   are evaluated in the page with `document.evaluate` rather than with `DOM.performSearch`,
+///   which can only search the whole page and also matches text and attribute values.
+/// * `text=Save`: elements whose visible text contains "Save", ignoring case and runs of
+///   whitespace. Quoting the text, as in `text="Save"`, only matches elements whose text is
+///   exactly that. Of several nested matches, only the innermost is returned.
+/// * `pierce=my-dialog button`: a CSS selector which also matches inside open shadow roots. Each
+///   shadow root is queried separately, so one selector can't span a shadow boundary.
+///
+/// Text and piercing selectors look inside open shadow roots, CSS and XPath don't.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Selector {
+    Css(String),
+    XPath(String),
+    Text { text: String, exact: bool },
+    Pierce(String),
+}
+
+impl From<&str> for Selector {
+    fn from(selector: &str) -> Self {
+        let (engine, body) = match selector.find('=') {
+            Some(index) => (&selector[..index], &selector[index + 1..]),
+            None => ("", selector),
+        };
+        match engine {
+            "css" => Self::Css(body.to_string()),
+            "xpath" => Self::XPath(body.to_string()),
+            "pierce" => Self::Pierce(body.to_string()),
+            "text" => {
+                let quoted = body.len() >= 2 && body.starts_with('"') && body.ends_with('"');
+                if quoted {
+                    Self::Text {
+                        text: body[1..body.len() - 1].to_string(),
+                        exact: true,
+                    }
+                } else {
+                    Self::Text {
+                        text: body.to_string(),
+                        exact: false,
+                    }
+                }
+            }
+            _ if selector.starts_with("//") || selector.starts_with("..") => {
+                Self::XPath(selector.to_string())
+            }
+            _ => Self::Css(selector.to_string()),
+        }
+    }
+}
+
+impl From<&String> for Selector {
+    fn from(selector: &String) -> Self {
+        Self::from(selector.as_str())
+    }
+}
+
+impl From<String> for Selector {
+    fn from(selector: String) -> Self {
+        Self::from(selector.as_str())
+    }
+}
+
+impl From<&Selector> for Selector {
+    fn from(selector: &Selector) -> Self {
+        selector.clone()
+    }
+}
+
+impl fmt::Display for Selector {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Css(css) => write!(f, "{}", css),
+            Self::XPath(expression) => write!(f, "xpath={}", expression),
+            Self::Text { text, exact: true } => write!(f, "text=\"{}\"", text),
+            Self::Text { text, exact: false } => write!(f, "text={}", text),
+            Self::Pierce(css) => write!(f, "pierce={}", css),
+        }
+    }
+}
+
+// Called with the node being queried as `this`, these return an array of the elements found.
+// XPath isn't looked up with DOM.performSearch, because that can only search the whole page and
+// also returns nodes whose text or attributes merely contain the expression. An absolute path
+// would search the whole document whatever the context node is, so below an element one is made
+// relative to it.
+const QUERY_XPATH_JS: &str = "function(expression) {
+    const document = this.ownerDocument || this;
+    if (this !== document && expression.startsWith('/'))
+        expression = '.' + expression;
+    const result = document.evaluate(
+        expression, this, null, XPathResult.ORDERED_NODE_SNAPSHOT_TYPE, null);
+    const elements = [];
+    for (let i = 0; i < result.snapshotLength; i++) {
+        const node = result.snapshotItem(i);
+        if (node.nodeType === Node.ELEMENT_NODE)
+            elements.push(node);
+    }
+    return elements;
+}";
+
+const QUERY_TEXT_JS: &str = "function(text, exact) {
+    const normalize = text => text.replace(/\\s+/g, ' ').trim();
+    const wanted = exact ? normalize(text) : normalize(text).toLowerCase();
+    const matches = element => {
+        if (element.getClientRects().length === 0
+            || getComputedStyle(element).visibility === 'hidden')
+            return false;
+        const content = normalize(element.innerText || element.textContent || '');
+        return exact ? content === wanted : content.toLowerCase().includes(wanted);
+    };
+
+    const found = [];
+    const visit = root => {
+        for (const element of root.querySelectorAll('*')) {
+            if (matches(element))
+                found.push(element);
+            if (element.shadowRoot)
+                visit(element.shadowRoot);
+        }
+    };
+    visit(this);
+    if (this.shadowRoot)
+        visit(this.shadowRoot);
+
+    // whether `node` is inside `ancestor`, counting shadow roots as inside their hosts
+    const contains = (ancestor, node) => {
+        for (let parent = node.parentNode || node.host; parent;
+             parent = parent.parentNode || parent.host) {
+            if (parent === ancestor)
+                return true;
+        }
+        return false;
+    };
+    return found.filter(element => !found.some(other => contains(element, other)));
+}";
+
+impl Tab {
+    /// Returns the IDs of the elements below `node_id` which `selector` matches.
+    pub(crate) fn query_selector_all(
+        &self,
+        node_id: NodeId,
+        selector: &Selector,
+    ) -> Fallible<Vec<NodeId>> {
+        match selector {
+            Selector::Css(css) => Ok(self
+                .call_method(dom::methods::QuerySelectorAll {
+                    node_id,
+                    selector: css,
+                })
+                .map_err(NoElementFound::map)?
+                .node_ids),
+            Selector::XPath(expression) => {
+                let arguments = serde_json::to_string(expression)?;
+                self.query_with_js(node_id, QUERY_XPATH_JS, &arguments)
+            }
+            Selector::Text { text, exact } => {
+                let arguments = format!("{}, {}", serde_json::to_string(text)?, exact);
+                self.query_with_js(node_id, QUERY_TEXT_JS, &arguments)
+            }
+            Selector::Pierce(css) => self.query_selector_all_piercing(node_id, css),
+        }
+    }
+
+    fn backend_node_id(&self, node_id: NodeId) -> Fallible<NodeId> {
+        Ok(self
+            .call_method(dom::methods::DescribeNode {
+                node_id: Some(node_id),
+                backend_node_id: None,
+                depth: Some(0),
+                pierce: None,
+            })
+            .map_err(NoElementFound::map)?
+            .node
+            .backend_node_id)
+    }
+
+    fn query_with_js(
+        &self,
+        node_id: NodeId,
+        query: &str,
+        arguments: &str,
+    ) -> Fallible<Vec<NodeId>> {
+        let object_id = self
+            .call_method(dom::methods::ResolveNode {
+                backend_node_id: Some(self.backend_node_id(node_id)?),
+            })?
+            .object
+            .object_id
+            .ok_or_else(|| format_err!("Couldn't resolve node {}", node_id))?;
+
+        let function_declaration = format!(
+            "function() {{ return ({}).call(this, {}); }}",
+            query, arguments
+        );
+        let return_object = self.call_method(runtime::methods::CallFunctionOn {
+            object_id: &object_id,
+            function_declaration: &function_declaration,
+            arguments: None,
+            return_by_value: false,
+            generate_preview: false,
+            silent: true,
+            await_promise: false,
+        })?;
+        if let Some(exception_details) = return_object.exception_details {
+            let description = exception_details
+                .exception
+                .and_then(|exception| exception.description)
+                .unwrap_or(exception_details.text);
+            return Err(format_err!("Invalid selector: {}", description));
+        }
+        let array_id = return_object
+            .result
+            .object_id
+            .ok_or_else(|| format_err!("Querying node {} didn't return an array", node_id))?;
+
+        let mut elements: Vec<(usize, String)> = self
+            .call_method(runtime::methods::GetProperties {
+                object_id: &array_id,
+                own_properties: true,
+            })?
+            .result
+            .into_iter()
+            .filter_map(|property| {
+                let index = property.name.parse().ok()?;
+                Some((index, property.value?.object_id?))
+            })
+            .collect();
+        elements.sort();
+
+        elements
+            .iter()
+            .map(|(_, object_id)| {
+                Ok(self
+                    .call_method(dom::methods::RequestNode { object_id })?
+                    .node_id)
+            })
+            .collect()
+    }
+
+    fn query_selector_all_piercing(&self, node_id: NodeId, css: &str) -> Fallible<Vec<NodeId>> {
+        // describing the subtree, unlike getting the whole document, leaves the node IDs handed
+        // out so far valid, so the shadow roots are found by their backend IDs
+        let node = self
+            .call_method(dom::methods::DescribeNode {
+                node_id: Some(node_id),
+                backend_node_id: None,
+                depth: Some(-1),
+                pierce: Some(true),
+            })
+            .map_err(NoElementFound::map)?
+            .node;
+
+        let mut roots = vec![node_id];
+        let mut shadow_roots = Vec::new();
+        collect_shadow_roots(&node, &mut shadow_roots);
+        for backend_node_id in shadow_roots {
+            let object_id = self
+                .call_method(dom::methods::ResolveNode {
+                    backend_node_id: Some(backend_node_id),
+                })?
+                .object
+                .object_id
+                .ok_or_else(|| format_err!("Couldn't resolve node {}", backend_node_id))?;
+            roots.push(
+                self.call_method(dom::methods::RequestNode {
+                    object_id: &object_id,
+                })?
+                .node_id,
+            );
+        }
+
+        let mut node_ids = Vec::new();
+        for root in roots {
+            node_ids.extend(
+                self.call_method(dom::methods::QuerySelectorAll {
+                    node_id: root,
+                    selector: css,
+                })?
+                .node_ids,
+            );
+        }
+        Ok(node_ids)
+    }
+}
+
+// adds the backend IDs of the shadow roots below `node` which pages can see into, without
+// descending into other frames
+fn collect_shadow_roots(node: &Node, roots: &mut Vec<NodeId>) {
+    for shadow_root in node.shadow_roots.iter().flatten() {
+        if let Some(ShadowRootType::UserAgent) = shadow_root.shadow_root_type {
+            continue;
+        }
+        roots.push(shadow_root.backend_node_id);
+        collect_shadow_roots(shadow_root, roots);
+    }
+    for child in node.children.iter().flatten() {
+        collect_shadow_roots(child, roots);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Selector;
+
+    #[test]
+    fn parses_selectors() {
+        assert_eq!(
+            Selector::from("div#foobar > a"),
+            Selector::Css("div#foobar > a".to_string())
+        );
+        assert_eq!(
+            Selector::from("a[href='/?a=b']"),
+            Selector::Css("a[href='/?a=b']".to_string())
+        );
+        assert_eq!(
+            Selector::from("css=a[href='/?a=b']"),
+            Selector::Css("a[href='/?a=b']".to_string())
+        );
+        assert_eq!(
+            Selector::from("//button[@type='submit']"),
+            Selector::XPath("//button[@type='submit']".to_string())
+        );
+        assert_eq!(
+            Selector::from("xpath=./li"),
+            Selector::XPath("./li".to_string())
+        );
+        assert_eq!(
+            Selector::from("text=Save changes"),
+            Selector::Text {
+                text: "Save changes".to_string(),
+                exact: false
+            }
+        );
+        assert_eq!(
+            Selector::from("text=\"Save\""),
+            Selector::Text {
+                text: "Save".to_string(),
+                exact: true
+            }
+        );
+        assert_eq!(
+            Selector::from("pierce=my-dialog button"),
+            Selector::Pierce("my-dialog button".to_string())
+        );
+    }
+
+    #[test]
+    fn displays_selectors() {
+        for selector in &[
+            "div > a",
+            "xpath=//a",
+            "text=Save",
+            "text=\"Save\"",
+            "pierce=a",
+        ] {
+            assert_eq!(Selector::from(*selector).to_string(), *selector);
+        }
+    }
+}
@@ -0,0 +1,138 @@
+use failure::Fallible;
+use log::*;
+
+use crate::browser::tab::point::Point;
+use crate::browser::tab::Tab;
+use crate::protocol::input::{self, TouchPoint};
+
+/// The touchscreen of a tab, for pages which only listen to touch events.
+///
+/// Touch events only reach pages which think there's a touchscreen, so touch emulation usually
+/// needs enabling first:
+///
+/// ```rust,no_run
+/// # use failure::Fallible;
+/// # fn main() -> Fallible<()> {
+/// # use headless_chrome::Browser;
+/// # use headless_chrome::browser::tab::Point;
+/// # let browser = Browser::default()?;
+/// let tab = browser.wait_for_initial_tab()?;
+/// tab.set_touch_emulation_enabled(true)?;
+/// tab.navigate_to("https://m.example.com")?.wait_until_navigated()?;
+///
+/// tab.find_element("button.menu")?.tap()?;
+/// let touchscreen = tab.touchscreen();
+/// touchscreen.swipe(Point { x: 300.0, y: 200.0 }, Point { x: 20.0, y: 200.0 })?;
+/// touchscreen.pinch(Point { x: 200.0, y: 300.0 }, 2.0)?;
+/// #
+/// # Ok(())
+/// # }
+/// ```
+pub struct Touchscreen<'a> {
+    tab: &'a Tab,
+}
+
+impl<'a> Touchscreen<'a> {
+    pub fn new(tab: &'a Tab) -> Self {
+        Self { tab }
+    }
+
+    /// Touches `point` and lifts the finger again.
+    pub fn tap(&self, point: Point) -> Fallible<&Self> {
+        trace!("Tapping {:?}", point);
+        self.tab.optional_slow_motion_sleep(250);
+        self.start(&[point])?;
+        self.end()
+    }
+
+    /// Puts a finger down on each of `points` at once, which fires `touchstart`.
+    pub fn start(&self, points: &[Point]) -> Fallible<&Self> {
+        self.dispatch("touchStart", points)
+    }
+
+    /// Moves the fingers touching the screen to `points`, in the same order they were put down
+    /// in, which fires `touchmove`.
+    pub fn move_to(&self, points: &[Point]) -> Fallible<&Self> {
+        self.dispatch("touchMove", points)
+    }
+
+    /// Lifts all fingers, which fires `touchend`.
+    pub fn end(&self) -> Fallible<&Self> {
+        self.dispatch("touchEnd", &[])
+    }
+
+    /// Cancels the touches in progress, which fires `touchcancel`.
+    pub fn cancel(&self) -> Fallible<&Self> {
+        self.dispatch("touchCancel", &[])
+    }
+
+    /// Swipes a finger from `from` to `to`, with everything in between a real swipe would fire,
+    /// returning once the page has scrolled.
+    pub fn swipe(&self, from: Point, to: Point) -> Fallible<&Self> {
+        trace!("Swiping from {:?} to {:?}", from, to);
+        self.tab
+            .call_method(input::methods::SynthesizeScrollGesture {
+                x: from.x,
+                y: from.y,
+                x_distance: Some(to.x - from.x),
+                y_distance: Some(to.y - from.y),
+                gesture_source_type: Some("touch"),
+                ..Default::default()
+            })?;
+        Ok(self)
+    }
+
+    /// Scrolls what's under `point` by `delta_x` and `delta_y` pixels with a swipe. Positive
+    /// values scroll right and down, so the finger moves the other way.
+    pub fn scroll(&self, point: Point, delta_x: f64, delta_y: f64) -> Fallible<&Self> {
+        self.swipe(
+            point,
+            point
+                - Point {
+                    x: delta_x,
+                    y: delta_y,
+                },
+        )
+    }
+
+    /// Pinches two fingers around `center` until what's under them is `scale_factor` times the
+    /// size, i.e. zooms in for more than 1 and out for less.
+    pub fn pinch(&self, center: Point, scale_factor: f64) -> Fallible<&Self> {
+        trace!("Pinching {:?} by {}", center, scale_factor);
+        self.tab
+            .call_method(input::methods::SynthesizePinchGesture {
+                x: center.x,
+                y: center.y,
+                scale_factor,
+                gesture_source_type: Some("touch"),
+                ..Default::default()
+            })?;
+        Ok(self)
+    }
+
+    fn dispatch(&self, event_type: &str, points: &[Point]) -> Fallible<&Self> {
+        let modifiers = *self.tab.modifiers.lock().unwrap();
+        self.tab.call_method(input::methods::DispatchTouchEvent {
+            event_type,
+            touch_points: points
+                .iter()
+                .zip(0_u32..)
+                .map(|(point, id)| TouchPoint {
+                    x: point.x,
+                    y: point.y,
+                    id: Some(f64::from(id)),
+                    ..Default::default()
+                })
+                .collect(),
+            modifiers: Some(modifiers.bits()),
+        })?;
+        Ok(self)
+    }
+}
+
+impl Tab {
+    /// Returns the tab's [`Touchscreen`](touchscreen/struct.Touchscreen.html).
+    pub fn touchscreen(&self) -> Touchscreen<'_> {
+        Touchscreen::new(self)
+    }
+}
@@ -80,6 +80,33 @@ pub enum WindowState {
     Fullscreen,
 }
 
+/// A permission that can be granted to an origin via `Browser.grantPermissions`.
+/// See https://chromedevtools.github.io/devtools-protocol/tot/Browser#type-PermissionType
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub enum Permission {
+    AccessibilityEvents,
+    AudioCapture,
+    BackgroundSync,
+    BackgroundFetch,
+    ClipboardReadWrite,
+    ClipboardSanitizedWrite,
+    DurableStorage,
+    Flash,
+    Geolocation,
+    Midi,
+    MidiSysex,
+    Notifications,
+    PaymentHandler,
+    PeriodicBackgroundSync,
+    ProtectedMediaIdentifier,
+    Sensors,
+    VideoCapture,
+    IdleDetection,
+    WakeLockScreen,
+    WakeLockSystem,
+}
+
 #[derive(Clone, Debug)]
 pub struct CurrentBounds {
     pub left: JsUInt,
@@ -104,7 +131,7 @@ impl From<methods::Bounds> for CurrentBounds {
 pub mod methods {
     use crate::protocol::Method;
 
-    use super::{Deserialize, JsUInt, Serialize, WindowState};
+    use super::{Deserialize, JsUInt, Permission, Serialize, WindowState};
 
     #[derive(Serialize, Debug)]
     pub struct GetVersion {}
@@ -176,4 +203,54 @@ pub mod methods {
         const NAME: &'static str = "Browser.getWindowForTarget";
         type ReturnObject = GetWindowForTargetReturnObject;
     }
+
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GrantPermissions<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub origin: Option<&'a str>,
+        pub permissions: &'a [Permission],
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub browser_context_id: Option<&'a str>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct GrantPermissionsReturnObject {}
+
+    impl<'a> Method for GrantPermissions<'a> {
+        const NAME: &'static str = "Browser.grantPermissions";
+        type ReturnObject = GrantPermissionsReturnObject;
+    }
+
+    #[derive(Debug, Serialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ResetPermissions<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub browser_context_id: Option<&'a str>,
+    }
+
+    #[derive(Debug, Deserialize)]
+    pub struct ResetPermissionsReturnObject {}
+
+    impl<'a> Method for ResetPermissions<'a> {
+        const NAME: &'static str = "Browser.resetPermissions";
+        type ReturnObject = ResetPermissionsReturnObject;
+    }
+}
+
+#[test]
+fn permissions_serialize_as_protocol_names() {
+    let permissions = [Permission::Geolocation, Permission::ClipboardReadWrite];
+    let method = methods::GrantPermissions {
+        origin: Some("https://example.com"),
+        permissions: &permissions,
+        browser_context_id: None,
+    };
+    assert_eq!(
+        serde_json::to_value(&method).unwrap(),
+        serde_json::json!({
+            "origin": "https://example.com",
+            "permissions": ["geolocation", "clipboardReadWrite"]
+        })
+    );
 }
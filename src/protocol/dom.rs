@@ -159,6 +159,8 @@ pub mod methods {
         #[serde(skip_serializing_if = "Option::is_none")]
         pub backend_node_id: Option<super::NodeId>,
         pub depth: Option<JsInt>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub pierce: Option<bool>,
     }
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
@@ -259,6 +261,23 @@ pub mod methods {
         type ReturnObject = ResolveNodeReturnObject;
     }
 
+    /// Requests that the node for a JavaScript object is sent to the caller, so that it gets a
+    /// `NodeId` which can be used with the other DOM methods.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RequestNode<'a> {
+        pub object_id: &'a str,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RequestNodeReturnObject {
+        pub node_id: super::NodeId,
+    }
+    impl<'a> Method for RequestNode<'a> {
+        const NAME: &'static str = "DOM.requestNode";
+        type ReturnObject = RequestNodeReturnObject;
+    }
+
     #[derive(Serialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct GetContentQuads<'a> {
@@ -307,4 +326,31 @@ pub mod methods {
         const NAME: &'static str = "DOM.getBoxModel";
         type ReturnObject = GetBoxModelReturnObject;
     }
+
+    /// Returns the node at the given location, in CSS pixels relative to the viewport.
+    #[derive(Serialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetNodeForLocation {
+        pub x: JsInt,
+        pub y: JsInt,
+        #[serde(
+            rename = "includeUserAgentShadowDOM",
+            skip_serializing_if = "Option::is_none"
+        )]
+        pub include_user_agent_shadow_dom: Option<bool>,
+        /// Whether to also hit elements with `pointer-events: none`.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub ignore_pointer_events_none: Option<bool>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetNodeForLocationReturnObject {
+        pub backend_node_id: super::NodeId,
+        pub frame_id: Option<String>,
+        pub node_id: Option<super::NodeId>,
+    }
+    impl Method for GetNodeForLocation {
+        const NAME: &'static str = "DOM.getNodeForLocation";
+        type ReturnObject = GetNodeForLocationReturnObject;
+    }
 }
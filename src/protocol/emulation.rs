@@ -0,0 +1,204 @@
+use serde::Serialize;
+
+use crate::protocol::types::JsUInt;
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum ScreenOrientationType {
+    PortraitPrimary,
+    PortraitSecondary,
+    LandscapePrimary,
+    LandscapeSecondary,
+}
+
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScreenOrientation {
+    #[serde(rename = "type")]
+    pub orientation_type: ScreenOrientationType,
+    pub angle: JsUInt,
+}
+
+/// A vision deficiency to simulate, by filtering what's rendered.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub enum VisionDeficiency {
+    /// Turns the simulation off.
+    None,
+    Achromatopsia,
+    BlurredVision,
+    ReducedContrast,
+    Deuteranopia,
+    Protanopia,
+    Tritanopia,
+}
+
+#[derive(Serialize, Debug, Clone, PartialEq, Eq)]
+#[serde(rename_all = "camelCase")]
+pub struct MediaFeature<'a> {
+    pub name: &'a str,
+    pub value: &'a str,
+}
+
+pub mod methods {
+    use serde::{Deserialize, Serialize};
+
+    use crate::protocol::types::{JsFloat, JsUInt};
+    use crate::protocol::Method;
+
+    /// Overrides the Geolocation Position or Error. Omitting any of the parameters emulates
+    /// position unavailable.
+    #[derive(Serialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetGeolocationOverride {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub latitude: Option<JsFloat>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub longitude: Option<JsFloat>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub accuracy: Option<JsFloat>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetGeolocationOverrideReturnObject {}
+    impl Method for SetGeolocationOverride {
+        const NAME: &'static str = "Emulation.setGeolocationOverride";
+        type ReturnObject = SetGeolocationOverrideReturnObject;
+    }
+
+    /// Enables touch on platforms which don't support it, so pages see a touchscreen.
+    #[derive(Serialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetTouchEmulationEnabled {
+        pub enabled: bool,
+        /// Maximum touch points supported. Defaults to one.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub max_touch_points: Option<JsUInt>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetTouchEmulationEnabledReturnObject {}
+    impl Method for SetTouchEmulationEnabled {
+        const NAME: &'static str = "Emulation.setTouchEmulationEnabled";
+        type ReturnObject = SetTouchEmulationEnabledReturnObject;
+    }
+
+    /// Overrides the values of device screen dimensions (`window.screen.width`,
+    /// `window.screen.height`, `window.innerWidth`, `window.innerHeight`, and "device-width" /
+    /// "device-height"-related CSS media query results).
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetDeviceMetricsOverride {
+        /// Overriding width value in pixels (minimum 0, maximum 10000000). 0 disables the
+        /// override.
+        pub width: JsUInt,
+        /// Overriding height value in pixels (minimum 0, maximum 10000000). 0 disables the
+        /// override.
+        pub height: JsUInt,
+        /// Overriding device scale factor value. 0 disables the override.
+        pub device_scale_factor: JsFloat,
+        /// Whether to emulate a mobile device. This includes the viewport meta tag, overlay
+        /// scrollbars, text autosizing and more.
+        pub mobile: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub screen_orientation: Option<super::ScreenOrientation>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetDeviceMetricsOverrideReturnObject {}
+    impl Method for SetDeviceMetricsOverride {
+        const NAME: &'static str = "Emulation.setDeviceMetricsOverride";
+        type ReturnObject = SetDeviceMetricsOverrideReturnObject;
+    }
+
+    /// Clears the overridden device metrics.
+    #[derive(Serialize, Debug)]
+    pub struct ClearDeviceMetricsOverride {}
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ClearDeviceMetricsOverrideReturnObject {}
+    impl Method for ClearDeviceMetricsOverride {
+        const NAME: &'static str = "Emulation.clearDeviceMetricsOverride";
+        type ReturnObject = ClearDeviceMetricsOverrideReturnObject;
+    }
+
+    /// Overrides the default timezone of the host system with the specified one.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetTimezoneOverride<'a> {
+        /// The timezone identifier, e.g. "Europe/Berlin". If empty, disables the override and
+        /// restores the default host system timezone.
+        pub timezone_id: &'a str,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetTimezoneOverrideReturnObject {}
+    impl<'a> Method for SetTimezoneOverride<'a> {
+        const NAME: &'static str = "Emulation.setTimezoneOverride";
+        type ReturnObject = SetTimezoneOverrideReturnObject;
+    }
+
+    /// Overrides the default host system locale with the specified one.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetLocaleOverride<'a> {
+        /// ICU style C locale (e.g. "en_US"). If not specified or empty, disables the override
+        /// and restores the default host system locale.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub locale: Option<&'a str>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetLocaleOverrideReturnObject {}
+    impl<'a> Method for SetLocaleOverride<'a> {
+        const NAME: &'static str = "Emulation.setLocaleOverride";
+        type ReturnObject = SetLocaleOverrideReturnObject;
+    }
+
+    /// Emulates the given media type or media feature for CSS media queries.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetEmulatedMedia<'a> {
+        /// Media type to emulate, e.g. "print" or "screen". Empty string disables the override.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub media: Option<&'a str>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub features: Option<Vec<super::MediaFeature<'a>>>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetEmulatedMediaReturnObject {}
+    impl<'a> Method for SetEmulatedMedia<'a> {
+        const NAME: &'static str = "Emulation.setEmulatedMedia";
+        type ReturnObject = SetEmulatedMediaReturnObject;
+    }
+
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetEmulatedVisionDeficiency {
+        #[serde(rename = "type")]
+        pub deficiency_type: super::VisionDeficiency,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetEmulatedVisionDeficiencyReturnObject {}
+    impl Method for SetEmulatedVisionDeficiency {
+        const NAME: &'static str = "Emulation.setEmulatedVisionDeficiency";
+        type ReturnObject = SetEmulatedVisionDeficiencyReturnObject;
+    }
+
+    /// Enables CPU throttling to emulate slow CPUs.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetCPUThrottlingRate {
+        /// Throttling rate as a slowdown factor (1 is no throttle, 2 is 2x slowdown, etc).
+        pub rate: JsFloat,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetCPUThrottlingRateReturnObject {}
+    impl Method for SetCPUThrottlingRate {
+        const NAME: &'static str = "Emulation.setCPUThrottlingRate";
+        type ReturnObject = SetCPUThrottlingRateReturnObject;
+    }
+}
@@ -1,3 +1,51 @@
+use serde::{Deserialize, Serialize};
+
+use crate::protocol::types::{JsFloat, JsInt};
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DragDataItem {
+    pub mime_type: String,
+    /// Depending of the value of `mime_type`, it contains the dragged link, text, HTML markup or
+    /// any other data.
+    pub data: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base_url: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct DragData {
+    pub items: Vec<DragDataItem>,
+    /// List of filenames that should be included when dropping.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub files: Option<Vec<String>>,
+    /// Bit field of the allowed drag operations: Copy=1, Link=2, Move=16.
+    pub drag_operations_mask: JsInt,
+}
+
+/// A finger touching the screen.
+#[derive(Serialize, Debug, Clone, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct TouchPoint {
+    pub x: JsFloat,
+    pub y: JsFloat,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius_x: Option<JsFloat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub radius_y: Option<JsFloat>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rotation_angle: Option<JsFloat>,
+    /// Between 0 and 1.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub force: Option<JsFloat>,
+    /// Identifies the point across events, so it can be told apart from other fingers.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<JsFloat>,
+}
+
 pub mod methods {
     use serde::{Deserialize, Serialize};
 
@@ -15,6 +63,18 @@ pub mod methods {
         pub button: Option<&'a str>,
         #[serde(skip_serializing_if = "Option::is_none")]
         pub click_count: Option<JsUInt>,
+        /// Bit field of the buttons held down: Left=1, Right=2, Middle=4, Back=8, Forward=16.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub buttons: Option<JsUInt>,
+        /// Bit field of the modifier keys held down: Alt=1, Ctrl=2, Meta/Command=4, Shift=8.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub modifiers: Option<JsUInt>,
+        /// X delta in CSS pixels for mouse wheel events.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub delta_x: Option<JsFloat>,
+        /// Y delta in CSS pixels for mouse wheel events.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub delta_y: Option<JsFloat>,
     }
     impl<'a> Default for DispatchMouseEvent<'a> {
         fn default() -> Self {
@@ -24,6 +84,10 @@ pub mod methods {
                 y: 0.0,
                 button: None,
                 click_count: None,
+                buttons: None,
+                modifiers: None,
+                delta_x: None,
+                delta_y: None,
             }
         }
     }
@@ -48,6 +112,9 @@ pub mod methods {
         pub code: Option<&'a str>,
         pub windows_virtual_key_code: JsUInt,
         pub native_virtual_key_code: JsUInt,
+        /// Bit field of the modifier keys held down: Alt=1, Ctrl=2, Meta/Command=4, Shift=8.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub modifiers: Option<JsUInt>,
     }
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
@@ -56,4 +123,147 @@ pub mod methods {
         const NAME: &'static str = "Input.dispatchKeyEvent";
         type ReturnObject = DispatchKeyEventReturnObject;
     }
+
+    /// Inserts text as if it came from an IME or emoji keyboard, without any key events.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct InsertText<'a> {
+        pub text: &'a str,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct InsertTextReturnObject {}
+    impl<'a> Method for InsertText<'a> {
+        const NAME: &'static str = "Input.insertText";
+        type ReturnObject = InsertTextReturnObject;
+    }
+
+    /// Makes Chrome emit `Input.dragIntercepted` instead of carrying out drag and drop
+    /// operations started with the mouse, so they can be finished with `DispatchDragEvent`.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetInterceptDrags {
+        pub enabled: bool,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetInterceptDragsReturnObject {}
+    impl Method for SetInterceptDrags {
+        const NAME: &'static str = "Input.setInterceptDrags";
+        type ReturnObject = SetInterceptDragsReturnObject;
+    }
+
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DispatchDragEvent<'a> {
+        /// Allowed values: dragEnter, dragOver, drop, dragCancel
+        #[serde(rename = "type")]
+        pub event_type: &'a str,
+        pub x: JsFloat,
+        pub y: JsFloat,
+        pub data: &'a super::DragData,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub modifiers: Option<JsUInt>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DispatchDragEventReturnObject {}
+    impl<'a> Method for DispatchDragEvent<'a> {
+        const NAME: &'static str = "Input.dispatchDragEvent";
+        type ReturnObject = DispatchDragEventReturnObject;
+    }
+
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DispatchTouchEvent<'a> {
+        /// Allowed values: touchStart, touchEnd, touchMove, touchCancel
+        #[serde(rename = "type")]
+        pub event_type: &'a str,
+        /// All the points touching the screen after this event; empty for touchEnd and
+        /// touchCancel.
+        pub touch_points: Vec<super::TouchPoint>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub modifiers: Option<JsUInt>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DispatchTouchEventReturnObject {}
+    impl<'a> Method for DispatchTouchEvent<'a> {
+        const NAME: &'static str = "Input.dispatchTouchEvent";
+        type ReturnObject = DispatchTouchEventReturnObject;
+    }
+
+    /// Synthesizes a scroll gesture over a time period, returning once it's done.
+    #[derive(Serialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SynthesizeScrollGesture<'a> {
+        pub x: JsFloat,
+        pub y: JsFloat,
+        /// The distance to scroll along the X axis (positive to scroll left).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub x_distance: Option<JsFloat>,
+        /// The distance to scroll along the Y axis (positive to scroll up).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub y_distance: Option<JsFloat>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub x_overscroll: Option<JsFloat>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub y_overscroll: Option<JsFloat>,
+        /// Prevent fling (default: true).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub prevent_fling: Option<bool>,
+        /// Swipe speed in pixels per second (default: 800).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub speed: Option<JsUInt>,
+        /// Allowed values: default, touch, mouse
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gesture_source_type: Option<&'a str>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SynthesizeScrollGestureReturnObject {}
+    impl<'a> Method for SynthesizeScrollGesture<'a> {
+        const NAME: &'static str = "Input.synthesizeScrollGesture";
+        type ReturnObject = SynthesizeScrollGestureReturnObject;
+    }
+
+    /// Synthesizes a pinch gesture over a time period, returning once it's done.
+    #[derive(Serialize, Debug, Default)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SynthesizePinchGesture<'a> {
+        pub x: JsFloat,
+        pub y: JsFloat,
+        /// Relative scale factor after zooming (>1.0 zooms in, <1.0 zooms out).
+        pub scale_factor: JsFloat,
+        /// Relative pointer speed in pixels per second (default: 800).
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub relative_speed: Option<JsUInt>,
+        /// Allowed values: default, touch, mouse
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub gesture_source_type: Option<&'a str>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SynthesizePinchGestureReturnObject {}
+    impl<'a> Method for SynthesizePinchGesture<'a> {
+        const NAME: &'static str = "Input.synthesizePinchGesture";
+        type ReturnObject = SynthesizePinchGestureReturnObject;
+    }
+}
+
+pub mod events {
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DragInterceptedEventParams {
+        pub data: super::DragData,
+    }
+
+    /// Emitted when a drag is started with the mouse while drags are intercepted.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct DragInterceptedEvent {
+        pub params: DragInterceptedEventParams,
+    }
 }
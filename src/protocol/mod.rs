@@ -11,6 +11,7 @@ use serde_json::Value;
 pub mod browser;
 pub mod debugger;
 pub mod dom;
+pub mod emulation;
 pub mod fetch;
 pub mod input;
 pub mod logs;
@@ -111,14 +112,28 @@ pub enum Event {
     FrameStartedLoading(page::events::FrameStartedLoadingEvent),
     #[serde(rename = "Page.frameNavigated")]
     FrameNavigated(page::events::FrameNavigatedEvent),
+    #[serde(rename = "Page.frameAttached")]
+    FrameAttached(page::events::FrameAttachedEvent),
+    #[serde(rename = "Page.frameDetached")]
+    FrameDetached(page::events::FrameDetachedEvent),
     #[serde(rename = "Page.frameStoppedLoading")]
     FrameStoppedLoading(page::events::FrameStoppedLoadingEvent),
     #[serde(rename = "Page.lifecycleEvent")]
     Lifecycle(page::events::LifecycleEvent),
+    #[serde(rename = "Page.javascriptDialogOpening")]
+    JavascriptDialogOpening(page::events::JavascriptDialogOpeningEvent),
     #[serde(rename = "Network.requestIntercepted")]
     RequestIntercepted(network::events::RequestInterceptedEvent),
     #[serde(rename = "Network.responseReceived")]
     ResponseReceived(network::events::ResponseReceivedEvent),
+    #[serde(rename = "Network.requestWillBeSent")]
+    RequestWillBeSent(network::events::RequestWillBeSentEvent),
+    #[serde(rename = "Network.loadingFinished")]
+    LoadingFinished(network::events::LoadingFinishedEvent),
+    #[serde(rename = "Network.loadingFailed")]
+    LoadingFailed(network::events::LoadingFailedEvent),
+    #[serde(rename = "Input.dragIntercepted")]
+    DragIntercepted(input::events::DragInterceptedEvent),
     #[serde(rename = "Fetch.requestPaused")]
     RequestPaused(fetch::events::RequestPausedEvent),
     #[serde(rename = "Fetch.authRequired")]
@@ -127,6 +142,14 @@ pub enum Event {
     LogEntryAdded(logs::events::EntryAddedEvent),
     #[serde(rename = "Runtime.exceptionThrown")]
     RuntimeExceptionThrown(runtime::events::ExceptionThrownEvent),
+    #[serde(rename = "Runtime.consoleAPICalled")]
+    ConsoleAPICalled(runtime::events::ConsoleAPICalledEvent),
+    #[serde(rename = "Runtime.executionContextCreated")]
+    ExecutionContextCreated(runtime::events::ExecutionContextCreatedEvent),
+    #[serde(rename = "Runtime.executionContextDestroyed")]
+    ExecutionContextDestroyed(runtime::events::ExecutionContextDestroyedEvent),
+    #[serde(rename = "Runtime.executionContextsCleared")]
+    ExecutionContextsCleared(runtime::events::ExecutionContextsClearedEvent),
 }
 
 #[derive(Deserialize, Debug, Clone)]
@@ -208,6 +231,25 @@ mod tests {
             }
             _ => panic!("bad news"),
         }
+
+        let dialog_opening_event = json!({
+            "method": "Page.javascriptDialogOpening",
+            "params": {
+                "url": "https://example.com/",
+                "message": "What's your name?",
+                "type": "prompt",
+                "hasBrowserHandler": false,
+                "defaultPrompt": "Anonymous"
+            }
+        });
+        let event: Event = serde_json::from_value(dialog_opening_event).unwrap();
+        match event {
+            Event::JavascriptDialogOpening(ev) => {
+                assert_eq!(ev.params.dialog_type, page::DialogType::Prompt);
+                assert_eq!(ev.params.default_prompt.as_ref().unwrap(), "Anonymous");
+            }
+            _ => panic!("bad news"),
+        }
     }
 
     #[test]
@@ -59,6 +59,61 @@ pub enum CookieSameSite {
     None,
 }
 
+/// Network conditions to emulate, see `Tab::emulate_network_conditions`.
+///
+/// The presets are those of Chrome's DevTools.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct NetworkConditions {
+    pub offline: bool,
+    /// Minimum latency from request sent to response headers received, in milliseconds.
+    pub latency: JsFloat,
+    /// Maximum download throughput in bytes per second, or -1 for no limit.
+    pub download_throughput: JsFloat,
+    /// Maximum upload throughput in bytes per second, or -1 for no limit.
+    pub upload_throughput: JsFloat,
+}
+
+impl NetworkConditions {
+    pub const NO_THROTTLING: Self = Self {
+        offline: false,
+        latency: 0.0,
+        download_throughput: -1.0,
+        upload_throughput: -1.0,
+    };
+
+    /// Every request fails as if there was no internet connection.
+    pub const OFFLINE: Self = Self {
+        offline: true,
+        latency: 0.0,
+        download_throughput: 0.0,
+        upload_throughput: 0.0,
+    };
+
+    /// 400 kbit/s both ways, with 2 s of latency.
+    pub const SLOW_3G: Self = Self {
+        offline: false,
+        latency: 2000.0,
+        download_throughput: 50_000.0,
+        upload_throughput: 50_000.0,
+    };
+
+    /// 1.44 Mbit/s down and 675 kbit/s up, with 562.5 ms of latency.
+    pub const FAST_3G: Self = Self {
+        offline: false,
+        latency: 562.5,
+        download_throughput: 180_000.0,
+        upload_throughput: 84_375.0,
+    };
+
+    /// 8.1 Mbit/s down and 1.35 Mbit/s up, with 165 ms of latency.
+    pub const SLOW_4G: Self = Self {
+        offline: false,
+        latency: 165.0,
+        download_throughput: 1_012_500.0,
+        upload_throughput: 168_750.0,
+    };
+}
+
 #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
 #[serde(rename_all = "camelCase")]
 pub struct Cookie {
@@ -170,12 +225,16 @@ pub mod events {
         TextTrack,
         XHR,
         Fetch,
+        Prefetch,
         EventSource,
         WebSocket,
         Manifest,
         SignedExchange,
         Ping,
         CSPViolationReport,
+        Preflight,
+        /// Also stands for types Chrome added after this was written.
+        #[serde(other)]
         Other,
     }
 
@@ -203,6 +262,59 @@ pub mod events {
         pub params: ResponseReceivedEventParams,
     }
 
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RequestWillBeSentEventParams {
+        pub request_id: String,
+        pub loader_id: String,
+        #[serde(rename = "documentURL")]
+        pub document_url: String,
+        pub request: super::Request,
+        pub timestamp: JsFloat,
+        /// Set when this request is a redirect of an earlier one with the same id.
+        pub redirect_response: Option<super::Response>,
+        #[serde(rename = "type")]
+        pub _type: Option<ResourceType>,
+        pub frame_id: Option<String>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct RequestWillBeSentEvent {
+        pub params: RequestWillBeSentEventParams,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LoadingFinishedEventParams {
+        pub request_id: String,
+        pub timestamp: JsFloat,
+        pub encoded_data_length: JsFloat,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LoadingFinishedEvent {
+        pub params: LoadingFinishedEventParams,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LoadingFailedEventParams {
+        pub request_id: String,
+        pub timestamp: JsFloat,
+        #[serde(rename = "type")]
+        pub _type: ResourceType,
+        pub error_text: String,
+        pub canceled: Option<bool>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct LoadingFailedEvent {
+        pub params: LoadingFailedEventParams,
+    }
+
     #[test]
     fn can_parse_request_intercepted_event() {
         use crate::protocol;
@@ -234,12 +346,76 @@ pub mod events {
                 .unwrap();
         let _event = serde_json::from_value::<protocol::Message>(json_message).unwrap();
     }
+
+    #[test]
+    fn can_parse_request_will_be_sent_event() {
+        use crate::protocol;
+        use serde_json::json;
+
+        let json_message = json!({
+            "method":"Network.requestWillBeSent",
+            "params":{
+                "documentURL":"http://127.0.0.1:38157/",
+                "frameId":"41AF9B7E70803C38860A845DBEB8F85F",
+                "hasUserGesture":false,
+                "initiator":{"type":"script"},
+                "loaderId":"2F3C5B0E9D1A7B6C8E4F0A1B2C3D4E5F",
+                "request":{
+                    "hasPostData":true,
+                    "headers":{"Content-Type":"application/json"},
+                    "initialPriority":"High",
+                    "method":"POST",
+                    "postData":"{\"name\":\"apple\"}",
+                    "referrerPolicy":"strict-origin-when-cross-origin",
+                    "url":"http://127.0.0.1:38157/api/items"
+                },
+                "requestId":"1000.3",
+                "timestamp":1234.5,
+                "type":"Fetch",
+                "wallTime":1_567_000_000.5
+            }
+        });
+
+        match serde_json::from_value::<protocol::Message>(json_message).unwrap() {
+            protocol::Message::Event(protocol::Event::RequestWillBeSent(event)) => {
+                assert_eq!(event.params.request.method, "POST");
+                assert_eq!(event.params._type, Some(ResourceType::Fetch));
+            }
+            _ => panic!("Expected a Network.requestWillBeSent event"),
+        }
+    }
+
+    #[test]
+    fn can_parse_loading_failed_event_of_unknown_type() {
+        use crate::protocol;
+        use serde_json::json;
+
+        let json_message = json!({
+            "method":"Network.loadingFailed",
+            "params":{
+                "requestId":"1000.4",
+                "timestamp":1234.5,
+                "type":"SomethingNew",
+                "errorText":"net::ERR_FAILED",
+                "canceled":false
+            }
+        });
+
+        match serde_json::from_value::<protocol::Message>(json_message).unwrap() {
+            protocol::Message::Event(protocol::Event::LoadingFailed(event)) => {
+                assert_eq!(event.params._type, ResourceType::Other);
+                assert_eq!(event.params.error_text, "net::ERR_FAILED");
+            }
+            _ => panic!("Expected a Network.loadingFailed event"),
+        }
+    }
 }
 
 pub mod methods {
     use serde::{Deserialize, Serialize};
 
     use crate::protocol::network::Cookie;
+    use crate::protocol::types::JsFloat;
     use crate::protocol::Method;
     use std::collections::HashMap;
 
@@ -325,6 +501,23 @@ pub mod methods {
         type ReturnObject = GetResponseBodyReturnObject;
     }
 
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetRequestPostData<'a> {
+        pub request_id: &'a str,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetRequestPostDataReturnObject {
+        pub post_data: String,
+    }
+
+    impl<'a> Method for GetRequestPostData<'a> {
+        const NAME: &'static str = "Network.getRequestPostData";
+        type ReturnObject = GetRequestPostDataReturnObject;
+    }
+
     #[derive(Serialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct SetUserAgentOverride<'a, 'b, 'c> {
@@ -390,4 +583,27 @@ pub mod methods {
         const NAME: &'static str = "Network.setExtraHTTPHeaders";
         type ReturnObject = SetExtraHTTPHeadersReturnObject;
     }
+
+    /// Activates emulation of network conditions.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EmulateNetworkConditions {
+        /// True to emulate internet disconnection.
+        pub offline: bool,
+        /// Minimum latency from request sent to response headers received (ms).
+        pub latency: JsFloat,
+        /// Maximal aggregated download throughput (bytes/sec). -1 disables download throttling.
+        pub download_throughput: JsFloat,
+        /// Maximal aggregated upload throughput (bytes/sec). -1 disables upload throttling.
+        pub upload_throughput: JsFloat,
+    }
+
+    #[derive(Deserialize, Debug, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct EmulateNetworkConditionsReturnObject {}
+
+    impl Method for EmulateNetworkConditions {
+        const NAME: &'static str = "Network.emulateNetworkConditions";
+        type ReturnObject = EmulateNetworkConditionsReturnObject;
+    }
 }
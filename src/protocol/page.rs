@@ -1,4 +1,4 @@
-use crate::protocol::types::{JsFloat, JsUInt};
+use crate::protocol::types::{JsFloat, JsInt, JsUInt};
 use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -14,6 +14,47 @@ pub struct Frame {
     pub unreachable_url: Option<String>,
 }
 
+/// How the user got to a page.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TransitionType {
+    Link,
+    Typed,
+    AddressBar,
+    AutoBookmark,
+    AutoSubframe,
+    ManualSubframe,
+    Generated,
+    AutoToplevel,
+    FormSubmit,
+    Reload,
+    Keyword,
+    KeywordGenerated,
+    /// Also stands for transition types Chrome added after this was written.
+    #[serde(other)]
+    Other,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationEntry {
+    pub id: JsInt,
+    pub url: String,
+    /// URL that the user typed in the URL bar.
+    #[serde(rename = "userTypedURL")]
+    pub user_typed_url: String,
+    pub title: String,
+    pub transition_type: TransitionType,
+}
+
+#[derive(Deserialize, Debug, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NavigationHistory {
+    /// Index of the current entry in `entries`.
+    pub current_index: JsInt,
+    pub entries: Vec<NavigationEntry>,
+}
+
 #[derive(Debug, Serialize)]
 #[serde(rename_all = "lowercase")]
 pub(crate) enum InternalScreenshotFormat {
@@ -79,6 +120,18 @@ pub struct PrintToPdfOptions {
     pub prefer_css_page_size: Option<bool>,
 }
 
+/// The kind of a JavaScript dialog, i.e. which function opened it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum DialogType {
+    Alert,
+    Confirm,
+    Prompt,
+    /// Asks whether to leave the page, see
+    /// [`beforeunload`](https://developer.mozilla.org/en-US/docs/Web/API/Window/beforeunload_event).
+    Beforeunload,
+}
+
 pub mod events {
     use serde::Deserialize;
 
@@ -115,6 +168,27 @@ pub mod events {
         pub frame: super::Frame,
     }
 
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    pub struct FrameAttachedEvent {
+        pub params: FrameAttachedParams,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct FrameAttachedParams {
+        pub frame_id: String,
+        pub parent_frame_id: String,
+    }
+
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    pub struct FrameDetachedEvent {
+        pub params: FrameDetachedParams,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct FrameDetachedParams {
+        pub frame_id: String,
+    }
+
     #[derive(Deserialize, Debug, Clone, PartialEq)]
     pub struct FrameStoppedLoadingEvent {
         pub params: FrameStoppedLoadingParams,
@@ -124,6 +198,24 @@ pub mod events {
     pub struct FrameStoppedLoadingParams {
         pub frame_id: String,
     }
+
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    pub struct JavascriptDialogOpeningEvent {
+        pub params: JavascriptDialogOpeningParams,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct JavascriptDialogOpeningParams {
+        /// The URL of the frame which opened the dialog.
+        pub url: String,
+        pub message: String,
+        #[serde(rename = "type")]
+        pub dialog_type: super::DialogType,
+        /// Whether Chrome would handle the dialog itself if it isn't handled over the protocol.
+        pub has_browser_handler: bool,
+        /// The text a prompt is pre-filled with.
+        pub default_prompt: Option<String>,
+    }
 }
 
 pub mod methods {
@@ -132,7 +224,7 @@ pub mod methods {
     use crate::protocol::Method;
 
     use super::PrintToPdfOptions;
-    use crate::protocol::types::JsUInt;
+    use crate::protocol::types::{JsInt, JsUInt};
 
     #[derive(Serialize, Debug)]
     #[serde(rename_all = "camelCase")]
@@ -185,6 +277,42 @@ pub mod methods {
         type ReturnObject = ReloadReturnObject;
     }
 
+    /// Replaces the document of a frame with the given HTML.
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetDocumentContent<'a> {
+        pub frame_id: &'a str,
+        pub html: &'a str,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct SetDocumentContentReturnObject {}
+    impl<'a> Method for SetDocumentContent<'a> {
+        const NAME: &'static str = "Page.setDocumentContent";
+        type ReturnObject = SetDocumentContentReturnObject;
+    }
+
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetNavigationHistory {}
+    impl Method for GetNavigationHistory {
+        const NAME: &'static str = "Page.getNavigationHistory";
+        type ReturnObject = super::NavigationHistory;
+    }
+
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct NavigateToHistoryEntry {
+        pub entry_id: JsInt,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct NavigateToHistoryEntryReturnObject {}
+    impl Method for NavigateToHistoryEntry {
+        const NAME: &'static str = "Page.navigateToHistoryEntry";
+        type ReturnObject = NavigateToHistoryEntryReturnObject;
+    }
+
     #[derive(Serialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct SetLifecycleEventsEnabled {
@@ -223,6 +351,8 @@ pub mod methods {
     #[serde(rename_all = "camelCase")]
     pub struct Navigate<'a> {
         pub url: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub referrer: Option<&'a str>,
     }
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
@@ -293,4 +423,52 @@ pub mod methods {
         const NAME: &'static str = "Page.handleFileChooser";
         type ReturnObject = HandleFileChooserReturnObject;
     }
+
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HandleJavaScriptDialog<'a> {
+        pub accept: bool,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub prompt_text: Option<&'a str>,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct HandleJavaScriptDialogReturnObject {}
+    impl<'a> Method for HandleJavaScriptDialog<'a> {
+        const NAME: &'static str = "Page.handleJavaScriptDialog";
+        type ReturnObject = HandleJavaScriptDialogReturnObject;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn parses_history_with_unknown_transition_types() {
+        let history: NavigationHistory = serde_json::from_value(json!({
+            "currentIndex": 1,
+            "entries": [
+                {
+                    "id": 1,
+                    "url": "about:blank",
+                    "userTypedURL": "about:blank",
+                    "title": "",
+                    "transitionType": "typed"
+                },
+                {
+                    "id": 2,
+                    "url": "https://example.com/",
+                    "userTypedURL": "https://example.com/",
+                    "title": "Example Domain",
+                    "transitionType": "something_new"
+                }
+            ]
+        }))
+        .unwrap();
+        assert_eq!(history.entries[0].transition_type, TransitionType::Typed);
+        assert_eq!(history.entries[1].transition_type, TransitionType::Other);
+    }
 }
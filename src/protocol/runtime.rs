@@ -76,6 +76,8 @@ pub mod methods {
         pub value: Option<serde_json::Value>,
         pub unserializable_value: Option<String>,
         pub preview: Option<ObjectPreview>,
+        /// Unique object identifier, for non-primitive values.
+        pub object_id: Option<String>,
     }
 
     /// If debuggerId is set stack trace comes from another debugger and can be resolved there.
@@ -93,13 +95,13 @@ pub mod methods {
     #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
     #[serde(rename_all = "camelCase")]
     pub struct StackTrace {
-        description: Option<String>,
-        call_frames: Vec<CallFrame>,
+        pub description: Option<String>,
+        pub call_frames: Vec<CallFrame>,
         // parent: Option<StackTrace>,
         /// Asynchronous JavaScript stack trace that preceded this stack, if available.
         /// Experimental feature of DevTools
         /// See https://chromedevtools.github.io/devtools-protocol/tot/Runtime#type-StackTraceId
-        parent_id: Option<StackTraceId>,
+        pub parent_id: Option<StackTraceId>,
     }
 
     /// Stack entry for runtime errors and assertions
@@ -107,11 +109,23 @@ pub mod methods {
     #[derive(Debug, Clone, Deserialize, Serialize, PartialEq, Eq)]
     #[serde(rename_all = "camelCase")]
     pub struct CallFrame {
-        function_name: String,
-        script_id: ScriptId,
-        url: String,
-        line_number: JsInt,
-        column_number: JsInt,
+        pub function_name: String,
+        pub script_id: ScriptId,
+        pub url: String,
+        /// Counting from zero.
+        pub line_number: JsInt,
+        /// Counting from zero.
+        pub column_number: JsInt,
+    }
+
+    /// Either a JSON value or a remote object to call a function with.
+    #[derive(Serialize, Debug, Default, Clone)]
+    #[serde(rename_all = "camelCase")]
+    pub struct CallArgument<'a> {
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub value: Option<serde_json::Value>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub object_id: Option<&'a str>,
     }
 
     #[derive(Serialize, Debug, Default)]
@@ -119,6 +133,8 @@ pub mod methods {
     pub struct CallFunctionOn<'a> {
         pub object_id: &'a str,
         pub function_declaration: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub arguments: Option<Vec<CallArgument<'a>>>,
         pub return_by_value: bool,
         pub generate_preview: bool,
         pub silent: bool,
@@ -128,6 +144,8 @@ pub mod methods {
     #[serde(rename_all = "camelCase")]
     pub struct CallFunctionOnReturnObject {
         pub result: RemoteObject,
+        /// Set if the function threw, or returned a promise which was rejected.
+        pub exception_details: Option<super::events::ExceptionDetails>,
     }
     impl<'a> Method for CallFunctionOn<'a> {
         const NAME: &'static str = "Runtime.callFunctionOn";
@@ -144,17 +162,46 @@ pub mod methods {
         pub generate_preview: bool,
         pub user_gesture: bool,
         pub await_promise: bool,
+        /// Evaluate in this execution context instead of the main frame's.
+        #[serde(skip_serializing_if = "Option::is_none")]
+        pub context_id: Option<JsInt>,
     }
     #[derive(Debug, Deserialize)]
     #[serde(rename_all = "camelCase")]
     pub struct EvaluateReturnObject {
         pub result: RemoteObject,
+        /// Set if the expression threw, or evaluated to a promise which was rejected.
+        pub exception_details: Option<super::events::ExceptionDetails>,
     }
     impl<'a> Method for Evaluate<'a> {
         const NAME: &'static str = "Runtime.evaluate";
         type ReturnObject = EvaluateReturnObject;
     }
 
+    #[derive(Serialize, Debug)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetProperties<'a> {
+        pub object_id: &'a str,
+        /// Only return properties of the object itself, not of its prototype chain.
+        pub own_properties: bool,
+    }
+    #[derive(Deserialize, Debug, Clone, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct PropertyDescriptor {
+        pub name: String,
+        pub value: Option<RemoteObject>,
+        pub enumerable: bool,
+    }
+    #[derive(Debug, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    pub struct GetPropertiesReturnObject {
+        pub result: Vec<PropertyDescriptor>,
+    }
+    impl<'a> Method for GetProperties<'a> {
+        const NAME: &'static str = "Runtime.getProperties";
+        type ReturnObject = GetPropertiesReturnObject;
+    }
+
     #[derive(Serialize, Debug)]
     #[serde(rename_all = "camelCase")]
     pub struct Enable {}
@@ -214,6 +261,197 @@ pub mod events {
         pub params: ExceptionThrown,
     }
 
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ExecutionContextAuxData {
+        pub frame_id: Option<String>,
+        /// Whether this is the frame's main world, rather than one created by an extension or
+        /// via `Page.createIsolatedWorld`.
+        pub is_default: Option<bool>,
+    }
+
+    /// See https://chromedevtools.github.io/devtools-protocol/tot/Runtime#type-ExecutionContextDescription
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ExecutionContextDescription {
+        pub id: JsInt,
+        pub origin: String,
+        pub name: String,
+        pub aux_data: Option<ExecutionContextAuxData>,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ExecutionContextCreatedEvent {
+        pub params: ExecutionContextCreatedParams,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ExecutionContextCreatedParams {
+        pub context: ExecutionContextDescription,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ExecutionContextDestroyedEvent {
+        pub params: ExecutionContextDestroyedParams,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ExecutionContextDestroyedParams {
+        pub execution_context_id: JsInt,
+    }
+
+    /// Issued when all execution contexts were cleared, e.g. on navigation.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    pub struct ExecutionContextsClearedEvent {}
+
+    /// The `console` function a page called.
+    /// See https://chromedevtools.github.io/devtools-protocol/tot/Runtime#event-consoleAPICalled
+    #[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+    #[serde(rename_all = "camelCase")]
+    pub enum ConsoleApiType {
+        Log,
+        Debug,
+        Info,
+        Error,
+        Warning,
+        Dir,
+        Dirxml,
+        Table,
+        Trace,
+        Clear,
+        StartGroup,
+        StartGroupCollapsed,
+        EndGroup,
+        Assert,
+        Profile,
+        ProfileEnd,
+        Count,
+        TimeEnd,
+        /// A type Chrome added after this was written.
+        #[serde(other)]
+        Other,
+    }
+
+    /// Issued when a page calls `console.log` or one of the other `console` functions.
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ConsoleAPICalledEvent {
+        pub params: ConsoleAPICalledParams,
+    }
+
+    #[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+    #[serde(rename_all = "camelCase")]
+    pub struct ConsoleAPICalledParams {
+        #[serde(rename = "type")]
+        pub call_type: ConsoleApiType,
+        pub args: Vec<RemoteObject>,
+        pub execution_context_id: JsInt,
+        /// Milliseconds since epoch.
+        pub timestamp: f64,
+        /// Where the call was made from.
+        pub stack_trace: Option<StackTrace>,
+        /// The label of the console context, for calls made in one created with
+        /// `console.context()`.
+        pub context: Option<String>,
+    }
+
+    #[test]
+    fn can_parse_execution_context_created_event() {
+        let message = r#"
+          {
+            "method": "Runtime.executionContextCreated",
+            "params": {
+              "context": {
+                "id": 3,
+                "origin": "http://127.0.0.1:40135",
+                "name": "",
+                "uniqueId": "-3451254470573224911.2046596843474403126",
+                "auxData": {
+                  "isDefault": true,
+                  "type": "default",
+                  "frameId": "B7BB52DA9C2DB5BB2AD5DFB0ADF8C0B3"
+                }
+              }
+            }
+          }
+        "#;
+
+        match serde_json::from_str::<crate::protocol::Event>(message).unwrap() {
+            crate::protocol::Event::ExecutionContextCreated(event) => {
+                let aux_data = event.params.context.aux_data.unwrap();
+                assert_eq!(event.params.context.id, 3);
+                assert_eq!(
+                    aux_data.frame_id.as_ref().map(String::as_str),
+                    Some("B7BB52DA9C2DB5BB2AD5DFB0ADF8C0B3")
+                );
+                assert_eq!(aux_data.is_default, Some(true));
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
+
+    #[test]
+    fn can_parse_console_api_called_event() {
+        let message = r#"
+          {
+            "method": "Runtime.consoleAPICalled",
+            "params": {
+              "type": "warning",
+              "args": [
+                { "type": "string", "value": "Low on %s:" },
+                { "type": "number", "value": 3, "description": "3" }
+              ],
+              "executionContextId": 2,
+              "timestamp": 1566067104960.9648,
+              "stackTrace": {
+                "callFrames": [
+                  {
+                    "functionName": "check",
+                    "scriptId": "12",
+                    "url": "http://127.0.0.1:40135/",
+                    "lineNumber": 4,
+                    "columnNumber": 16
+                  }
+                ]
+              }
+            }
+          }
+        "#;
+
+        match serde_json::from_str::<crate::protocol::Event>(message).unwrap() {
+            crate::protocol::Event::ConsoleAPICalled(event) => {
+                assert_eq!(event.params.call_type, ConsoleApiType::Warning);
+                assert_eq!(event.params.args.len(), 2);
+                let stack_trace = event.params.stack_trace.unwrap();
+                assert_eq!(stack_trace.call_frames[0].function_name, "check");
+                assert_eq!(stack_trace.call_frames[0].line_number, 4);
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+
+        let message = r#"
+          {
+            "method": "Runtime.consoleAPICalled",
+            "params": {
+              "type": "somethingNew",
+              "args": [],
+              "executionContextId": 2,
+              "timestamp": 1566067104960.9648
+            }
+          }
+        "#;
+        match serde_json::from_str::<crate::protocol::Event>(message).unwrap() {
+            crate::protocol::Event::ConsoleAPICalled(event) => {
+                assert_eq!(event.params.call_type, ConsoleApiType::Other);
+            }
+            other => panic!("Unexpected event: {:?}", other),
+        }
+    }
+
     #[test]
     fn can_parse_exception_thrown_event() {
         let message = r#"
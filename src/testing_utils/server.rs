@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::{atomic, Arc};
 use std::thread::JoinHandle;
 use std::time::Duration;
@@ -122,3 +123,37 @@ pub fn file_server(path: &'static str) -> Server {
         }
     })
 }
+
+/// Serves the given files (keyed by URL path, e.g. `/Linux_x64/1/chrome-linux.zip`) as binary
+/// data, honouring `Range: bytes=N-` requests so that resumed downloads can be tested.
+#[allow(dead_code)]
+pub fn binary_server(files: HashMap<String, Vec<u8>>) -> Server {
+    Server::new(move |request: tiny_http::Request| {
+        let data = match files.get(request.url()) {
+            Some(data) => data,
+            None => return request.respond(not_found_response()),
+        };
+
+        let range_start = request
+            .headers()
+            .iter()
+            .find(|header| header.field.equiv("Range"))
+            .and_then(|header| {
+                let value = header.value.as_str();
+                value
+                    .trim_start_matches("bytes=")
+                    .trim_end_matches('-')
+                    .parse::<usize>()
+                    .ok()
+            });
+
+        match range_start {
+            Some(start) if start >= data.len() => {
+                request.respond(tiny_http::Response::new_empty(416.into()))
+            }
+            Some(start) => request
+                .respond(tiny_http::Response::from_data(&data[start..]).with_status_code(206)),
+            None => request.respond(tiny_http::Response::from_data(&data[..])),
+        }
+    })
+}
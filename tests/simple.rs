@@ -8,18 +8,20 @@ use failure::Fallible;
 use log::*;
 use rand::prelude::*;
 
-use headless_chrome::browser::tab::RequestPausedDecision;
+use headless_chrome::browser::tab::{
+    NavigateOptions, Polling, RequestPausedDecision, WaitForFunctionOptions, WaitUntil,
+};
 use headless_chrome::browser::transport::{SessionId, Transport};
 use headless_chrome::protocol::fetch::events::RequestPausedEvent;
-use headless_chrome::protocol::fetch::methods::{FulfilRequest, RequestPattern};
+use headless_chrome::protocol::fetch::methods::{FulfillRequest, RequestPattern};
 use headless_chrome::protocol::fetch::HeaderEntry;
 use headless_chrome::protocol::network::{Cookie, CookieParam};
 use headless_chrome::protocol::runtime::methods::{RemoteObjectSubtype, RemoteObjectType};
-use headless_chrome::protocol::RemoteError;
+use headless_chrome::protocol::{Event, RemoteError};
 use headless_chrome::util::Wait;
 use headless_chrome::{
-    protocol::browser::{Bounds, WindowState},
-    protocol::page::ScreenshotFormat,
+    protocol::browser::{Bounds, Permission, WindowState},
+    protocol::page::{ScreenshotFormat, TransitionType},
     Browser, Tab,
 };
 use std::collections::HashMap;
@@ -396,6 +398,1115 @@ document.write(navigator.userAgent + ";" + navigator.platform + ";" + navigator.
     Ok(())
 }
 
+#[test]
+fn navigate_with_returns_main_response() -> Fallible<()> {
+    logging::enable_logging();
+    let referrers = Arc::new(Mutex::new(Vec::new()));
+    let referrers_clone = Arc::clone(&referrers);
+    let responder = move |r: tiny_http::Request| {
+        if r.url() == "/with-referrer" {
+            let referrer = r
+                .headers()
+                .iter()
+                .find(|header| header.field.equiv("Referer"))
+                .map(|header| header.value.to_string());
+            referrers_clone.lock().unwrap().push(referrer);
+        }
+        let status = if r.url() == "/missing" { 404 } else { 200 };
+        let response = tiny_http::Response::new(
+            status.into(),
+            vec![tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap()],
+            std::io::Cursor::new("<html><body><img src='/image.png'></body></html>"),
+            None,
+            None,
+        );
+        r.respond(response)
+    };
+    let server = server::Server::new(responder);
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+
+    let response = tab
+        .navigate_with(&server.url(), NavigateOptions::default())?
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert_eq!(response.url, format!("{}/", server.url()));
+
+    let response = tab
+        .navigate_with(
+            &format!("{}/missing", server.url()),
+            NavigateOptions {
+                wait_until: WaitUntil::NetworkIdle0,
+                ..Default::default()
+            },
+        )?
+        .unwrap();
+    assert_eq!(response.status, 404);
+
+    tab.navigate_with(
+        &format!("{}/with-referrer", server.url()),
+        NavigateOptions {
+            wait_until: WaitUntil::DomContentLoaded,
+            referrer: Some("http://example.com/".to_string()),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(
+        *referrers.lock().unwrap(),
+        vec![Some("http://example.com/".to_string())]
+    );
+
+    // jumping to an anchor doesn't load a new document
+    let response = tab.navigate_with(
+        &format!("{}/with-referrer#anchor", server.url()),
+        NavigateOptions::default(),
+    )?;
+    assert!(response.is_none());
+    Ok(())
+}
+
+#[test]
+fn find_elements_in_frames() -> Fallible<()> {
+    logging::enable_logging();
+    let responder = move |r: tiny_http::Request| {
+        let body = if r.url() == "/widget" {
+            r#"<button id="pay">Pay</button><script>var inWidget = true;</script>"#
+        } else {
+            r#"<h1>Checkout</h1><iframe name="widget" src="/widget"></iframe>"#
+        };
+        let response = tiny_http::Response::new(
+            200.into(),
+            vec![tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap()],
+            std::io::Cursor::new(body),
+            None,
+            None,
+        );
+        r.respond(response)
+    };
+    let server = server::Server::new(responder);
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.navigate_with(&server.url(), NavigateOptions::default())?;
+
+    let frames = tab.frames()?;
+    assert_eq!(frames.len(), 2);
+    let main_frame = tab.main_frame()?;
+    assert!(main_frame.is_main_frame());
+    assert_eq!(main_frame.info.id, frames[0].info.id);
+
+    let widget = main_frame.child_frames()?.remove(0);
+    assert_eq!(widget.info.name.as_ref().map(String::as_str), Some("widget"));
+    assert!(widget.info.url.ends_with("/widget"));
+
+    assert!(tab.find_element("#pay").is_err());
+    assert_eq!(widget.find_element("#pay")?.get_inner_text()?, "Pay");
+    assert_eq!(main_frame.find_elements("h1")?.len(), 1);
+
+    assert_eq!(
+        widget.evaluate("typeof inWidget", false)?.value,
+        Some("boolean".into())
+    );
+    assert_eq!(
+        main_frame.evaluate("typeof inWidget", false)?.value,
+        Some("undefined".into())
+    );
+
+    let events = Arc::new(Mutex::new(Vec::new()));
+    let events_clone = Arc::clone(&events);
+    tab.add_event_listener(Arc::new(move |event: &Event| match event {
+        Event::FrameAttached(_) => events_clone.lock().unwrap().push("attached"),
+        Event::FrameDetached(_) => events_clone.lock().unwrap().push("detached"),
+        Event::FrameNavigated(_) => events_clone.lock().unwrap().push("navigated"),
+        _ => {}
+    }))?;
+    let wait_for_event = |name: &'static str| {
+        Wait::with_timeout(Duration::from_secs(5)).until(|| {
+            if events.lock().unwrap().contains(&name) {
+                Some(())
+            } else {
+                None
+            }
+        })
+    };
+    tab.evaluate("document.querySelector('iframe').src = '/widget?again'", false)?;
+    wait_for_event("navigated")?;
+    tab.evaluate("document.querySelector('iframe').remove()", false)?;
+    wait_for_event("detached")?;
+    tab.evaluate(
+        "document.body.appendChild(document.createElement('iframe'))",
+        false,
+    )?;
+    wait_for_event("attached")?;
+    Ok(())
+}
+
+#[test]
+fn wait_for_function() -> Fallible<()> {
+    logging::enable_logging();
+    let (_server, _browser, tab) = dumb_server(
+        r#"
+<html>
+<body>
+<script>
+setTimeout(() => { window.__APP_READY__ = { version: 3 }; }, 300);
+setTimeout(() => {
+  const late = document.createElement("div");
+  late.id = "late";
+  late.textContent = "here now";
+  document.body.appendChild(late);
+}, 300);
+</script>
+</body>
+</html>
+"#,
+    );
+
+    let ready = tab.wait_for_function(
+        "() => window.__APP_READY__",
+        &[],
+        WaitForFunctionOptions {
+            timeout: Some(Duration::from_secs(5)),
+            ..Default::default()
+        },
+    )?;
+    assert_eq!(ready["version"], 3);
+
+    let text = tab.wait_for_function(
+        "(id) => document.getElementById(id) && document.getElementById(id).textContent",
+        &["late".into()],
+        WaitForFunctionOptions {
+            polling: Polling::Mutation,
+            timeout: Some(Duration::from_secs(5)),
+        },
+    )?;
+    assert_eq!(text, "here now");
+
+    let error = tab
+        .wait_for_function(
+            "() => false",
+            &[],
+            WaitForFunctionOptions {
+                polling: Polling::Interval(50),
+                timeout: Some(Duration::from_millis(500)),
+            },
+        )
+        .unwrap_err();
+    assert!(error.downcast::<headless_chrome::util::Timeout>().is_ok());
+
+    assert!(tab
+        .wait_for_function(
+            "() => { throw new Error('broken'); }",
+            &[],
+            WaitForFunctionOptions::default()
+        )
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn expect_navigation() -> Fallible<()> {
+    logging::enable_logging();
+    let responder = move |r: tiny_http::Request| {
+        let (status, body) = match r.url() {
+            "/" => (200, r#"<a href="/next">next</a>"#),
+            "/next" => (200, "<h1>Next</h1>"),
+            _ => (404, "not found"),
+        };
+        let response = tiny_http::Response::new(
+            status.into(),
+            vec![tiny_http::Header::from_bytes(&b"Content-Type"[..], &b"text/html"[..]).unwrap()],
+            std::io::Cursor::new(body),
+            None,
+            None,
+        );
+        r.respond(response)
+    };
+    let server = server::Server::new(responder);
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.navigate_with(&server.url(), NavigateOptions::default())?;
+
+    let response = tab
+        .expect_navigation(NavigateOptions::default(), || {
+            tab.find_element("a")?.click().map(|_| ())
+        })?
+        .unwrap();
+    assert_eq!(response.status, 200);
+    assert!(response.url.ends_with("/next"));
+    tab.find_element("h1")?;
+
+    // a navigation which is already underway by the time the action returns isn't missed
+    let response = tab
+        .expect_navigation(
+            NavigateOptions {
+                wait_until: WaitUntil::DomContentLoaded,
+                ..Default::default()
+            },
+            || tab.evaluate("location.href = '/missing'", false),
+        )?
+        .unwrap();
+    assert_eq!(response.status, 404);
+    Ok(())
+}
+
+#[test]
+fn history_navigation() -> Fallible<()> {
+    logging::enable_logging();
+    let (server, _browser, tab) = dumb_server("<html><body>history</body></html>");
+    tab.wait_until_navigated()?;
+    tab.navigate_to(&format!("{}/one", server.url()))?
+        .wait_until_navigated()?;
+    tab.navigate_to(&format!("{}/two", server.url()))?
+        .wait_until_navigated()?;
+    let pathname = || -> Fallible<serde_json::Value> {
+        Ok(tab.evaluate("location.pathname", false)?.value.unwrap())
+    };
+
+    let history = tab.get_navigation_history()?;
+    let urls: Vec<_> = history.entries.iter().map(|entry| entry.url.clone()).collect();
+    assert!(urls.ends_with(&[format!("{}/one", server.url()), format!("{}/two", server.url())]));
+    assert_eq!(history.current_index as usize, urls.len() - 1);
+    assert_eq!(
+        history.entries.last().unwrap().transition_type,
+        TransitionType::Typed
+    );
+
+    tab.go_back()?.wait_until_navigated()?;
+    assert_eq!(pathname()?, "/one");
+    tab.go_forward()?.wait_until_navigated()?;
+    assert_eq!(pathname()?, "/two");
+    assert!(tab.go_forward().is_err());
+    Ok(())
+}
+
+#[test]
+fn set_content() -> Fallible<()> {
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+
+    tab.set_content(
+        r#"<!DOCTYPE html><p id="greeting">Hello</p><a id="link" href="next">next</a>"#,
+        Some("https://example.com/app/"),
+    )?;
+    assert_eq!(tab.find_element("#greeting")?.get_inner_text()?, "Hello");
+    assert_eq!(
+        tab.evaluate("document.getElementById('link').href", false)?
+            .value
+            .unwrap(),
+        "https://example.com/app/next"
+    );
+    assert_eq!(
+        tab.evaluate("document.compatMode", false)?.value.unwrap(),
+        "CSS1Compat"
+    );
+
+    tab.set_content("<p id='replaced'>Bye</p>", None)?;
+    assert!(tab.find_element("#greeting").is_err());
+    tab.find_element("#replaced")?;
+    Ok(())
+}
+
+#[test]
+fn wait_for_request_and_response() -> Fallible<()> {
+    logging::enable_logging();
+    let responder = move |r: tiny_http::Request| {
+        let (content_type, body) = if r.url() == "/api/items" {
+            ("application/json", r#"{"id":1}"#)
+        } else {
+            (
+                "text/html",
+                r#"<button id="save" onclick="fetch('/api/items', {
+                    method: 'POST',
+                    body: JSON.stringify({ name: 'apple' }),
+                })">Save</button>"#,
+            )
+        };
+        let response = tiny_http::Response::new(
+            200.into(),
+            vec![tiny_http::Header::from_bytes(&b"Content-Type"[..], content_type.as_bytes())
+                .unwrap()],
+            std::io::Cursor::new(body),
+            None,
+            None,
+        );
+        r.respond(response)
+    };
+    let server = server::Server::new(responder);
+    let (browser, tab) = dumb_client(&server);
+    tab.wait_until_navigated()?;
+    let click_soon = "setTimeout(() => document.getElementById('save').click(), 200)";
+
+    tab.evaluate(click_soon, false)?;
+    let request = tab.wait_for_request(
+        |request: &headless_chrome::protocol::network::Request| request.method == "POST",
+        Duration::from_secs(5),
+    )?;
+    assert_eq!(request.url(), format!("{}/api/items", server.url()));
+    let item: serde_json::Value = request.post_data_json()?;
+    assert_eq!(item, serde_json::json!({ "name": "apple" }));
+
+    tab.evaluate(click_soon, false)?;
+    let response = tab.wait_for_response("*/api/items", Duration::from_secs(5))?;
+    assert_eq!(response.status(), 200);
+    assert_eq!(response.json::<serde_json::Value>()?, serde_json::json!({ "id": 1 }));
+
+    assert!(tab
+        .wait_for_response("*/nothing", Duration::from_millis(500))
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn find_elements_with_selector_engines() -> Fallible<()> {
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<ul id="list"><li>Apples</li><li class="pear">Pears</li></ul>
+        <ol><li>Plums</li></ol>
+        <button hidden>Save</button>
+        <div><button id="save">Save  changes</button></div>
+        <fancy-dialog></fancy-dialog>
+        <script>
+            customElements.define('fancy-dialog', class extends HTMLElement {
+                constructor() {
+                    super();
+                    this.attachShadow({ mode: 'open' }).innerHTML =
+                        '<p><button class="confirm">Confirm</button></p>';
+                }
+            });
+        </script>"#,
+        None,
+    )?;
+    let id_of = |element: headless_chrome::browser::tab::element::Element| -> Fallible<String> {
+        Ok(element.get_attributes()?.unwrap_or_default()["id"].clone())
+    };
+
+    let items = tab.find_elements("//ul[@id='list']/li")?;
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[1].get_inner_text()?, "Pears");
+    assert_eq!(
+        tab.find_element("xpath=//li[text()='Apples']")?.get_inner_text()?,
+        "Apples"
+    );
+
+    assert_eq!(id_of(tab.find_element("text=save CHANGES")?)?, "save");
+    assert_eq!(id_of(tab.find_element(r#"text="Save changes""#)?)?, "save");
+    assert!(tab.find_element(r#"text="Save""#).is_err());
+
+    assert!(tab.find_element("button.confirm").is_err());
+    let confirm = tab.wait_for_element("pierce=button.confirm")?;
+    assert_eq!(confirm.get_inner_text()?, "Confirm");
+    assert_eq!(tab.find_element("text=Confirm")?.get_inner_text()?, "Confirm");
+
+    let list = tab.find_element("#list")?;
+    // looking up another element fetches the document again, which mustn't break `list`
+    tab.find_element("#save")?;
+    assert_eq!(list.find_elements("li")?.len(), 2);
+    assert_eq!(list.find_element("xpath=./li[2]")?.get_inner_text()?, "Pears");
+    // absolute paths are scoped to the element too, so the list next to it isn't searched
+    assert_eq!(list.find_elements("//li")?.len(), 2);
+    assert!(list.find_element("xpath=//li[text()='Plums']").is_err());
+    assert_eq!(tab.find_elements("//li")?.len(), 3);
+    assert_eq!(list.find_element("text=apples")?.get_inner_text()?, "Apples");
+    assert!(list.find_element("button").is_err());
+
+    assert!(tab.find_element("xpath=//li[").is_err());
+    Ok(())
+}
+
+#[test]
+fn locators_survive_re_renders() -> Fallible<()> {
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<div id="app"></div>
+        <script>
+            let count = 0;
+            const render = () => document.getElementById('app').innerHTML =
+                `<ul><li>first</li><li>second</li></ul>
+                <button onclick="count++">Add</button><span id="count">${count}</span>`;
+            render();
+            setInterval(render, 20);
+        </script>"#,
+        None,
+    )?;
+
+    let app = tab.locator("#app");
+    app.locator("text=Add").click()?;
+    app.locator("button").click()?;
+    Wait::with_timeout(Duration::from_secs(5)).until(|| {
+        match app.locator("#count").get_inner_text() {
+            Ok(ref count) if count == "2" => Some(()),
+            _ => None,
+        }
+    })?;
+
+    assert_eq!(app.locator("li").count()?, 2);
+    assert_eq!(app.locator("li").nth(1).get_inner_text()?, "second");
+    assert_eq!(tab.locator("#missing").count()?, 0);
+
+    tab.set_default_timeout(Duration::from_millis(500));
+    let start = Instant::now();
+    assert!(tab.locator("#missing").click().is_err());
+    assert!(start.elapsed() >= Duration::from_millis(500));
+    Ok(())
+}
+
+#[test]
+fn clicking_waits_for_elements_to_be_actionable() -> Fallible<()> {
+    use headless_chrome::browser::tab::element::NotActionable;
+
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<button id="target" onclick="window.clicked = true">Click me</button>
+        <button id="disabled" disabled>Nope</button>
+        <button id="gone" style="display: none">Gone</button>
+        <div class="modal" style="position: fixed; inset: 0"></div>"#,
+        None,
+    )?;
+    tab.set_default_timeout(Duration::from_millis(500));
+
+    let not_actionable = |selector: &str| -> Fallible<NotActionable> {
+        Ok(tab
+            .find_element(selector)?
+            .click()
+            .unwrap_err()
+            .downcast::<NotActionable>()?)
+    };
+    assert_eq!(
+        not_actionable("#target")?,
+        NotActionable::Covered("<div class=modal>".to_string())
+    );
+    assert_eq!(
+        not_actionable("#target")?.to_string(),
+        "element is covered by <div class=modal>"
+    );
+    assert_eq!(not_actionable("#disabled")?, NotActionable::Disabled);
+    assert_eq!(not_actionable("#gone")?, NotActionable::NotVisible);
+    assert_eq!(
+        tab.find_element("#gone")?
+            .get_midpoint()
+            .unwrap_err()
+            .downcast::<NotActionable>()?,
+        NotActionable::NotVisible
+    );
+
+    tab.evaluate(
+        "setTimeout(() => document.querySelector('.modal').remove(), 200)",
+        false,
+    )?;
+    tab.set_default_timeout(Duration::from_secs(5));
+    tab.find_element("#target")?.click()?;
+    assert_eq!(
+        tab.evaluate("window.clicked", false)?.value,
+        Some(serde_json::Value::Bool(true))
+    );
+    Ok(())
+}
+
+#[test]
+fn element_state_queries() -> Fallible<()> {
+    use headless_chrome::browser::tab::element::ElementDetached;
+
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<input id="name" value="initial" maxlength="20">
+        <input id="readonly" readonly>
+        <input id="agree" type="checkbox" checked>
+        <div id="switch" role="switch" aria-checked="false"></div>
+        <fieldset disabled><button id="disabled">Nope</button></fieldset>
+        <p id="text" style="color: rgb(255, 0, 0)">Hello <span hidden>there</span></p>
+        <div id="editable" contenteditable>edit me</div>
+        <div id="invisible" style="visibility: hidden">boo</div>"#,
+        None,
+    )?;
+
+    let name = tab.find_element("#name")?;
+    name.type_into("!")?;
+    assert_eq!(name.get_value()?, "initial!");
+    assert_eq!(name.get_property::<i64>("maxLength")?, 20);
+    assert_eq!(name.get_property::<Option<String>>("nonexistent")?, None);
+    assert!(name.is_editable()?);
+    assert!(!tab.find_element("#readonly")?.is_editable()?);
+    assert!(tab.find_element("#editable")?.is_editable()?);
+
+    assert!(tab.find_element("#agree")?.is_checked()?);
+    assert!(!tab.find_element("#switch")?.is_checked()?);
+    assert!(tab.find_element("#text")?.is_checked().is_err());
+
+    assert!(name.is_enabled()?);
+    assert!(!tab.find_element("#disabled")?.is_enabled()?);
+    assert!(!tab.find_element("#disabled")?.is_editable()?);
+
+    let text = tab.find_element("#text")?;
+    assert!(text.is_visible()?);
+    assert!(!tab.find_element("#invisible")?.is_visible()?);
+    assert_eq!(text.get_text_content()?, "Hello there");
+    assert_eq!(text.get_inner_text()?, "Hello");
+    assert_eq!(text.get_computed_style("color")?, "rgb(255, 0, 0)");
+    assert_eq!(
+        text.get_outer_html()?,
+        r#"<p id="text" style="color: rgb(255, 0, 0)">Hello <span hidden="">there</span></p>"#
+    );
+    assert!(text.get_value().is_err());
+
+    tab.evaluate("document.getElementById('text').remove()", false)?;
+    assert!(text
+        .get_text_content()
+        .unwrap_err()
+        .downcast::<ElementDetached>()
+        .is_ok());
+    assert!(!tab.locator("#text").is_visible()?);
+    Ok(())
+}
+
+#[test]
+fn fill_in_form_fields() -> Fallible<()> {
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<input id="name" value="initial">
+        <input id="born" type="date">
+        <textarea id="bio"></textarea>
+        <div id="notes" contenteditable>old notes</div>
+        <input id="readonly" readonly value="fixed">
+        <select id="country">
+            <option value="fr">France</option>
+            <option value="uk">United Kingdom</option>
+        </select>
+        <select id="languages" multiple>
+            <option value="en">English</option>
+            <option value="fr">French</option>
+            <option value="de">German</option>
+        </select>
+        <input id="newsletter" type="checkbox" checked>
+        <input id="terms" type="checkbox">
+        <input id="tea" type="radio" name="drink">
+        <input id="coffee" type="radio" name="drink">
+        <script>
+            window.events = [];
+            for (const type of ['input', 'change'])
+                document.addEventListener(type, event => events.push(type + ':' + event.target.id));
+        </script>"#,
+        None,
+    )?;
+
+    let name = tab.find_element("#name")?;
+    name.fill("Ada")?;
+    assert_eq!(name.get_value()?, "Ada");
+    let events = tab.evaluate("events.join(' ')", false)?.value.unwrap();
+    assert_eq!(events, "input:name change:name");
+    name.clear()?;
+    assert_eq!(name.get_value()?, "");
+
+    tab.find_element("#born")?.fill("1815-12-10")?;
+    assert_eq!(tab.find_element("#born")?.get_value()?, "1815-12-10");
+    assert!(tab.find_element("#born")?.fill("10/12/1815").is_err());
+    tab.locator("#bio").fill("Wrote the first program")?;
+    assert_eq!(tab.locator("#bio").get_value()?, "Wrote the first program");
+    tab.find_element("#notes")?.fill("new notes")?;
+    assert_eq!(tab.find_element("#notes")?.get_text_content()?, "new notes");
+    assert!(tab.find_element("#readonly")?.fill("changed").is_err());
+    assert!(tab.find_element("#terms")?.fill("changed").is_err());
+
+    let country = tab.find_element("#country")?;
+    assert_eq!(country.select_option(&["United Kingdom"])?, vec!["uk"]);
+    assert_eq!(country.get_value()?, "uk");
+    assert!(country.select_option(&["Germany"]).is_err());
+    let languages = tab.locator("#languages");
+    assert_eq!(languages.select_option(&["en", "German"])?, vec!["en", "de"]);
+
+    let newsletter = tab.find_element("#newsletter")?;
+    newsletter.check()?;
+    assert!(newsletter.is_checked()?);
+    newsletter.uncheck()?;
+    assert!(!newsletter.is_checked()?);
+    tab.locator("#terms").set_checked(true)?;
+    assert!(tab.find_element("#terms")?.is_checked()?);
+    tab.find_element("#tea")?.check()?;
+    // radio buttons can only be unchecked by checking another one
+    assert!(tab.find_element("#tea")?.uncheck().is_err());
+
+    let mut values = HashMap::new();
+    values.insert("#name", "Grace");
+    values.insert("#country", "fr");
+    values.insert("#newsletter", "true");
+    values.insert("#coffee", "true");
+    tab.fill_form(&values)?;
+    assert_eq!(name.get_value()?, "Grace");
+    assert_eq!(country.get_value()?, "fr");
+    assert!(newsletter.is_checked()?);
+    assert!(!tab.find_element("#tea")?.is_checked()?);
+    values.insert("#terms", "yes");
+    assert!(tab.fill_form(&values).is_err());
+    Ok(())
+}
+
+#[test]
+fn mouse_buttons_wheel_and_drag_and_drop() -> Fallible<()> {
+    use headless_chrome::browser::tab::mouse::{Modifiers, MouseButton};
+
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<style>
+            div { width: 100px; height: 100px; margin: 10px; }
+            #scroller { overflow: scroll; height: 100px; }
+        </style>
+        <div id="target"></div>
+        <div id="source" draggable="true" style="background: red"></div>
+        <div id="dropzone" style="background: blue"></div>
+        <div id="scroller"><div style="height: 1000px"></div></div>
+        <script>
+            window.events = [];
+            const target = document.getElementById('target');
+            for (const type of ['mousedown', 'mouseup', 'click', 'dblclick', 'contextmenu'])
+                target.addEventListener(type, event => {
+                    events.push(type + ':' + event.button + ':' + event.detail
+                        + (event.shiftKey ? ':shift' : ''));
+                    event.preventDefault();
+                });
+            document.getElementById('source').addEventListener('dragstart', event =>
+                event.dataTransfer.setData('text/plain', 'dragged'));
+            const dropzone = document.getElementById('dropzone');
+            dropzone.addEventListener('dragover', event => event.preventDefault());
+            dropzone.addEventListener('drop', event => {
+                event.preventDefault();
+                dropzone.textContent = event.dataTransfer.getData('text/plain');
+            });
+        </script>"#,
+        None,
+    )?;
+    let events = || -> Fallible<String> {
+        let events = tab.evaluate("events.splice(0).join(' ')", false)?.value;
+        Ok(events.unwrap().as_str().unwrap().to_string())
+    };
+
+    let target = tab.find_element("#target")?;
+    target.double_click()?;
+    assert_eq!(
+        events()?,
+        "mousedown:0:1 mouseup:0:1 click:0:1 mousedown:0:2 mouseup:0:2 click:0:2 dblclick:0:2"
+    );
+    target.right_click()?;
+    // whether contextmenu comes before or after mouseup depends on the platform
+    let right_click_events = events()?;
+    assert!(right_click_events.starts_with("mousedown:2:1"));
+    assert!(right_click_events.contains("contextmenu:2"));
+
+    let mouse = tab.mouse();
+    let midpoint = target.get_midpoint()?;
+    mouse.set_modifiers(Modifiers::SHIFT);
+    mouse.move_to(midpoint)?.down(MouseButton::Middle)?;
+    assert_eq!(mouse.position(), midpoint);
+    mouse.up(MouseButton::Middle)?;
+    mouse.set_modifiers(Modifiers::NONE);
+    assert_eq!(events()?, "mousedown:1:1:shift mouseup:1:1:shift");
+
+    let scroller = tab.find_element("#scroller")?;
+    mouse.move_to(scroller.get_midpoint()?)?.wheel(0.0, 300.0)?;
+    let scrolled = Wait::with_timeout(Duration::from_secs(2)).until(|| {
+        let scroll_top: f64 = scroller.get_property("scrollTop").ok()?;
+        if scroll_top > 0.0 {
+            Some(())
+        } else {
+            None
+        }
+    });
+    assert!(scrolled.is_ok());
+
+    tab.find_element("#source")?
+        .drag_to(&tab.find_element("#dropzone")?)?;
+    assert_eq!(
+        tab.find_element("#dropzone")?.get_text_content()?,
+        "dragged"
+    );
+    Ok(())
+}
+
+#[test]
+fn keyboard_chords_and_unicode_input() -> Fallible<()> {
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content(
+        r#"<input id="input">
+        <script>
+            window.keys = [];
+            const input = document.getElementById('input');
+            input.addEventListener('keydown', event => {
+                const modifiers = ['ctrlKey', 'shiftKey', 'altKey', 'metaKey']
+                    .filter(modifier => event[modifier])
+                    .map(modifier => modifier.replace('Key', ''));
+                keys.push([...modifiers, event.key].join('+'));
+            });
+        </script>"#,
+        None,
+    )?;
+    let input = tab.find_element("#input")?;
+    input.click()?;
+    let keys = || -> Fallible<String> {
+        let keys = tab.evaluate("keys.splice(0).join(',')", false)?.value;
+        Ok(keys.unwrap().as_str().unwrap().to_string())
+    };
+
+    let keyboard = tab.keyboard();
+    keyboard.type_str("Hi 世界 👋")?;
+    assert_eq!(input.get_value()?, "Hi 世界 👋");
+    // only characters which have a key fire key events
+    assert_eq!(keys()?, "H,i, , ");
+
+    keyboard.press("Control+Shift+K")?;
+    assert_eq!(keys()?, "ctrl+Control,ctrl+shift+Shift,ctrl+shift+K");
+    // shortcuts don't type anything
+    assert_eq!(input.get_value()?, "Hi 世界 👋");
+
+    keyboard.press("Control+a")?;
+    keyboard.down("Shift")?;
+    keyboard.press("x")?;
+    keyboard.press("KeyY")?;
+    keyboard.up("Shift")?;
+    keyboard.press("z")?;
+    assert_eq!(keys()?, "ctrl+Control,ctrl+a,shift+Shift,shift+x,shift+y,z");
+    assert_eq!(input.get_value()?, "xyz");
+
+    keyboard.insert_text("✓")?;
+    assert_eq!(input.get_value()?, "xyz✓");
+    assert!(keyboard.press("Control+").is_err());
+    assert!(keyboard.press("NoSuchKey").is_err());
+    Ok(())
+}
+
+#[test]
+fn touch_taps_and_gestures() -> Fallible<()> {
+    use headless_chrome::browser::tab::Point;
+
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_touch_emulation_enabled(true)?;
+    tab.set_content(
+        r#"<style>
+            #pad { width: 200px; height: 200px; }
+            #scroller { overflow: scroll; height: 200px; width: 200px; }
+        </style>
+        <div id="pad"></div>
+        <div id="scroller"><div style="height: 2000px"></div></div>
+        <script>
+            window.events = [];
+            const pad = document.getElementById('pad');
+            for (const type of ['touchstart', 'touchmove', 'touchend'])
+                pad.addEventListener(type, event => {
+                    events.push(type + ':' + event.touches.length);
+                    event.preventDefault();
+                }, { passive: false });
+        </script>"#,
+        None,
+    )?;
+    let events = || -> Fallible<String> {
+        let events = tab.evaluate("events.splice(0).join(' ')", false)?.value;
+        Ok(events.unwrap().as_str().unwrap().to_string())
+    };
+
+    let has_touch = tab.evaluate("'ontouchstart' in window", false)?.value;
+    assert_eq!(has_touch, Some(serde_json::Value::Bool(true)));
+
+    let pad = tab.find_element("#pad")?;
+    pad.tap()?;
+    assert_eq!(events()?, "touchstart:1 touchend:0");
+
+    let midpoint = pad.get_midpoint()?;
+    let touchscreen = tab.touchscreen();
+    let left = midpoint - Point { x: 20.0, y: 0.0 };
+    let right = midpoint + Point { x: 20.0, y: 0.0 };
+    touchscreen.start(&[left, right])?;
+    touchscreen.move_to(&[left - Point { x: 10.0, y: 0.0 }, right])?;
+    touchscreen.end()?;
+    assert_eq!(events()?, "touchstart:2 touchmove:2 touchend:0");
+
+    let scroller = tab.find_element("#scroller")?;
+    touchscreen.scroll(scroller.get_midpoint()?, 0.0, 100.0)?;
+    let scroll_top: f64 = scroller.get_property("scrollTop")?;
+    assert!(scroll_top > 0.0);
+
+    touchscreen.pinch(midpoint, 2.0)?;
+    Ok(())
+}
+
+#[test]
+fn emulate_devices() -> Fallible<()> {
+    use headless_chrome::browser::tab::device::{self, Device, Viewport};
+
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    let screen = || -> Fallible<serde_json::Value> {
+        Ok(tab
+            .evaluate(
+                "JSON.stringify({
+                    width: innerWidth,
+                    ratio: devicePixelRatio,
+                    touch: navigator.maxTouchPoints > 0,
+                    orientation: screen.orientation.type,
+                    iphone: navigator.userAgent.includes('iPhone'),
+                })",
+                false,
+            )?
+            .value
+            .map(|value| serde_json::from_str(value.as_str().unwrap()).unwrap())
+            .unwrap())
+    };
+
+    tab.emulate(&device::IPHONE_X)?;
+    tab.set_content("<p>Hello</p>", None)?;
+    assert_eq!(
+        screen()?,
+        serde_json::json!({
+            "width": 375,
+            "ratio": 3,
+            "touch": true,
+            "orientation": "portrait-primary",
+            "iphone": true,
+        })
+    );
+    let png = tab.capture_screenshot(ScreenshotFormat::PNG, None, true)?;
+    let (info, _) = png::Decoder::new(&png[..]).read_info()?;
+    assert_eq!((info.width, info.height), (375 * 3, 812 * 3));
+
+    tab.emulate(&device::IPHONE_X.landscape())?;
+    assert_eq!(screen()?["width"], 812);
+    assert_eq!(screen()?["orientation"], "landscape-primary");
+
+    let custom = Device {
+        name: "Kiosk",
+        user_agent: None,
+        viewport: Viewport {
+            width: 600,
+            height: 1000,
+            device_scale_factor: 1.5,
+            is_mobile: false,
+            has_touch: false,
+            is_landscape: false,
+        },
+    };
+    tab.emulate(&custom)?;
+    assert_eq!(screen()?["width"], 600);
+    assert_eq!(screen()?["ratio"], 1.5);
+    assert_eq!(screen()?["touch"], false);
+
+    tab.emulate(Device::find("pixel 2").unwrap())?;
+    assert_eq!(screen()?["width"], 411);
+    tab.clear_device_emulation()?;
+    assert_ne!(screen()?["width"], 411);
+    Ok(())
+}
+
+#[test]
+fn emulate_timezone_locale_and_media() -> Fallible<()> {
+    use headless_chrome::browser::tab::EmulationOptions;
+    use headless_chrome::protocol::emulation::VisionDeficiency;
+
+    logging::enable_logging();
+    let browser = browser();
+    let tab = browser.wait_for_initial_tab()?;
+    tab.set_content("<p>Hello</p>", None)?;
+    let evaluate = |tab: &Tab, expression: &str| -> Fallible<serde_json::Value> {
+        Ok(tab.evaluate(expression, false)?.value.unwrap())
+    };
+    let timezone = "Intl.DateTimeFormat().resolvedOptions().timeZone";
+
+    tab.set_timezone(Some("Asia/Tokyo"))?;
+    assert_eq!(evaluate(&tab, timezone)?, "Asia/Tokyo");
+    assert!(tab.set_timezone(Some("Mars/Olympus_Mons")).is_err());
+
+    tab.set_locale(Some("de-DE"))?;
+    assert_eq!(evaluate(&tab, "(1234.5).toLocaleString()")?, "1.234,5");
+    tab.set_locale(Some("en-US"))?;
+    assert_eq!(evaluate(&tab, "(1234.5).toLocaleString()")?, "1,234.5");
+
+    tab.set_emulated_media(
+        Some("print"),
+        &[
+            ("prefers-color-scheme", "dark"),
+            ("prefers-reduced-motion", "reduce"),
+        ],
+    )?;
+    assert_eq!(evaluate(&tab, "matchMedia('print').matches")?, true);
+    assert_eq!(
+        evaluate(&tab, "matchMedia('(prefers-color-scheme: dark)').matches")?,
+        true
+    );
+    assert_eq!(
+        evaluate(&tab, "matchMedia('(prefers-reduced-motion: reduce)').matches")?,
+        true
+    );
+    tab.set_emulated_media(None, &[])?;
+    assert_eq!(evaluate(&tab, "matchMedia('print').matches")?, false);
+
+    tab.set_vision_deficiency(VisionDeficiency::Deuteranopia)?;
+    tab.set_vision_deficiency(VisionDeficiency::None)?;
+
+    let context = browser.new_context()?.with_emulation_options(
+        EmulationOptions::new()
+            .with_timezone("America/New_York")
+            .with_media_feature("prefers-color-scheme", "dark"),
+    );
+    let context_tab = context.new_tab()?;
+    context_tab.set_content("<p>Hello</p>", None)?;
+    assert_eq!(evaluate(&context_tab, timezone)?, "America/New_York");
+    assert_eq!(
+        evaluate(
+            &context_tab,
+            "matchMedia('(prefers-color-scheme: dark)').matches"
+        )?,
+        true
+    );
+    Ok(())
+}
+
+#[test]
+fn emulate_network_conditions_and_cpu_throttling() -> Fallible<()> {
+    use headless_chrome::protocol::network::NetworkConditions;
+
+    logging::enable_logging();
+    let (server, browser, tab) = dumb_server(include_str!("simple.html"));
+    let fetch = || -> Fallible<serde_json::Value> {
+        Ok(tab
+            .evaluate(
+                "fetch(location.href + '?' + Math.random(), { cache: 'no-store' })
+                    .then(() => 'loaded', () => 'failed')",
+                true,
+            )?
+            .value
+            .unwrap())
+    };
+
+    tab.emulate_network_conditions(NetworkConditions::OFFLINE)?;
+    assert_eq!(fetch()?, "failed");
+    tab.emulate_network_conditions(NetworkConditions::NO_THROTTLING)?;
+    assert_eq!(fetch()?, "loaded");
+
+    tab.emulate_network_conditions(NetworkConditions {
+        latency: 1000.0,
+        ..NetworkConditions::NO_THROTTLING
+    })?;
+    let start = Instant::now();
+    assert_eq!(fetch()?, "loaded");
+    assert!(start.elapsed() >= Duration::from_millis(1000));
+    tab.emulate_network_conditions(NetworkConditions::NO_THROTTLING)?;
+
+    tab.set_cpu_throttling_rate(4.0)?;
+    tab.set_cpu_throttling_rate(1.0)?;
+    Ok(())
+}
+
+#[test]
+fn handle_javascript_dialogs() -> Fallible<()> {
+    use headless_chrome::browser::tab::dialog::{DialogHandler, DialogResponse};
+    use headless_chrome::protocol::page::DialogType;
+
+    logging::enable_logging();
+    let (server, browser, tab) = dumb_server(include_str!("simple.html"));
+    let evaluate = |expression: &str| -> Fallible<serde_json::Value> {
+        Ok(tab.evaluate(expression, false)?.value.unwrap())
+    };
+
+    tab.set_dialog_handler(DialogHandler::Accept);
+    assert_eq!(evaluate("confirm('Sure?')")?, true);
+    assert_eq!(evaluate("prompt('Name?', 'Anonymous')")?, "Anonymous");
+
+    tab.set_dialog_handler(DialogHandler::Dismiss);
+    assert_eq!(evaluate("confirm('Sure?')")?, false);
+    assert_eq!(evaluate("prompt('Name?') === null")?, true);
+
+    tab.set_dialog_handler(DialogHandler::Callback(Box::new(|dialog| {
+        if dialog.dialog_type == DialogType::Prompt {
+            DialogResponse::accept_with("Ferris")
+        } else {
+            DialogResponse::accept()
+        }
+    })));
+    assert_eq!(evaluate("prompt('Name?')")?, "Ferris");
+    assert_eq!(evaluate("alert('Hi'); 'done'")?, "done");
+
+    let dialogs = tab.get_dialogs();
+    assert_eq!(dialogs.len(), 6);
+    assert_eq!(dialogs[1].dialog_type, DialogType::Prompt);
+    assert_eq!(dialogs[1].default_prompt.as_ref().unwrap(), "Anonymous");
+    assert_eq!(dialogs[5].dialog_type, DialogType::Alert);
+    assert_eq!(dialogs[5].message, "Hi");
+    tab.clear_dialogs();
+    assert!(tab.get_dialogs().is_empty());
+
+    tab.evaluate(
+        "window.addEventListener('beforeunload', event => { event.preventDefault(); event.returnValue = ''; })",
+        false,
+    )?;
+    // Chrome only asks about leaving pages the user has interacted with
+    tab.find_element("body")?.click()?;
+    tab.set_dialog_handler(DialogHandler::Accept);
+    tab.close_with_unload()?;
+    let dialog = Wait::with_timeout(Duration::from_secs(5))
+        .until(|| tab.get_dialogs().first().cloned())?;
+    assert_eq!(dialog.dialog_type, DialogType::Beforeunload);
+    Ok(())
+}
+
+#[test]
+fn collect_console_messages() -> Fallible<()> {
+    use headless_chrome::protocol::runtime::events::ConsoleApiType;
+
+    logging::enable_logging();
+    let (server, browser, tab) = dumb_server(include_str!("simple.html"));
+    let console = tab.collect_console()?;
+    let errors = Arc::new(Mutex::new(Vec::new()));
+    let errors_clone = Arc::clone(&errors);
+    tab.on_console(move |message| {
+        if message.message_type == ConsoleApiType::Error {
+            errors_clone.lock().unwrap().push(message.text);
+        }
+    })?;
+
+    tab.evaluate(
+        "console.log('%s is %d', 'answer', 42.5, [1, 'two'], { a: null });
+        console.warn(undefined);
+        function fail() { console.error(new Error('boom').message); }
+        fail();",
+        false,
+    )?;
+    let messages = Wait::with_timeout(Duration::from_secs(5)).until(|| {
+        let messages = console.messages();
+        if messages.len() == 3 {
+            Some(messages)
+        } else {
+            None
+        }
+    })?;
+
+    assert_eq!(messages[0].message_type, ConsoleApiType::Log);
+    assert_eq!(messages[0].text, "answer is 42 [1, 'two'] {a: null}");
+    assert_eq!(messages[0].values[1], "answer");
+    assert_eq!(messages[0].args[2].object_type, RemoteObjectType::Number);
+    assert_eq!(messages[1].message_type, ConsoleApiType::Warning);
+    assert_eq!(messages[1].text, "undefined");
+    assert_eq!(messages[2].location().unwrap().function_name, "fail");
+    assert_eq!(*errors.lock().unwrap(), vec!["boom".to_string()]);
+    assert!(console.to_string().contains("[error] boom"));
+
+    console.clear();
+    assert!(console.messages().is_empty());
+    Ok(())
+}
+
 #[test]
 fn wait_for_element_returns_unexpected_errors_early() -> Fallible<()> {
     logging::enable_logging();
@@ -502,7 +1613,7 @@ fn set_request_interception() -> Fallible<()> {
             request_stage: Some("Request"),
         },
     ];
-    tab.enable_fetch(Some(&patterns), None)?;=
+    tab.enable_fetch(Some(&patterns), None)?;
 
     tab.enable_request_interception(Arc::new(
         move |transport: Arc<Transport>, session_id: SessionId, intercepted: RequestPausedEvent| {
@@ -778,3 +1889,19 @@ fn set_cookies() -> Fallible<()> {
     assert_eq!(cookies.len(), 1);
     Ok(())
 }
+
+#[test]
+fn set_geolocation() -> Fallible<()> {
+    logging::enable_logging();
+    let (server, browser, tab) = dumb_server(include_str!("simple.html"));
+    browser.grant_permissions(&server.url(), &[Permission::Geolocation])?;
+    tab.wait_until_navigated()?;
+    tab.set_geolocation(51.5, -0.12, 10.0)?;
+    let latitude = tab.evaluate(
+        "new Promise(resolve => navigator.geolocation.getCurrentPosition(p => resolve(p.coords.latitude)))",
+        true,
+    )?;
+    assert_eq!(latitude.value, Some((51.5).into()));
+    browser.reset_permissions()?;
+    Ok(())
+}